@@ -0,0 +1,92 @@
+use cosmwasm_std::Uint128;
+use sha2::{Digest, Sha256};
+
+use crate::error::ContractError;
+
+/// A SHA-256 digest, as used for leaves and nodes of an airdrop tree.
+pub type Hash = [u8; 32];
+
+/// Returns the leaf committing to `amount` for `address`: the SHA-256 of the address followed by the amount.
+pub fn leaf(address: &str, amount: Uint128) -> Hash {
+    Sha256::digest(format!("{address}{amount}")).into()
+}
+
+/// Returns the node above `a` and `b`. The pair is sorted first, so proofs need not record sides.
+pub fn parent(a: &Hash, b: &Hash) -> Hash {
+    let (first, second) = if a <= b { (a, b) } else { (b, a) };
+    let mut hasher = Sha256::new();
+    hasher.update(first);
+    hasher.update(second);
+    hasher.finalize().into()
+}
+
+/// Decodes a hex encoded hash, as found in registered roots and proofs.
+pub fn decode_hash(hex: &str) -> Result<Hash, ContractError> {
+    let bytes = hex::decode(hex).map_err(|_| ContractError::InvalidMerkleHash { hash: hex.to_string() })?;
+    bytes
+        .try_into()
+        .map_err(|_| ContractError::InvalidMerkleHash { hash: hex.to_string() })
+}
+
+/// Returns whether `proof` leads from the leaf of (`address`, `amount`) to `root`.
+pub fn verify(root: &Hash, address: &str, amount: Uint128, proof: &[String]) -> Result<bool, ContractError> {
+    let mut hash = leaf(address, amount);
+    for sibling in proof {
+        hash = parent(&hash, &decode_hash(sibling)?);
+    }
+    Ok(&hash == root)
+}
+
+/// Builds a tree over `leaves` and returns its root with the proof for every leaf, in the same order.
+/// A node without a sibling moves up a level unchanged and adds nothing to the proofs below it.
+pub fn build(leaves: &[Hash]) -> (Hash, Vec<Vec<Hash>>) {
+    let mut proofs = vec![Vec::new(); leaves.len()];
+    // Which node of the current level each leaf sits under
+    let mut positions: Vec<usize> = (0..leaves.len()).collect();
+    let mut level = leaves.to_vec();
+    while level.len() > 1 {
+        for (proof, position) in proofs.iter_mut().zip(positions.iter_mut()) {
+            if let Some(sibling) = level.get(*position ^ 1) {
+                proof.push(*sibling);
+            }
+            *position /= 2;
+        }
+        level = level
+            .chunks(2)
+            .map(|pair| match pair {
+                [a, b] => parent(a, b),
+                [a] => *a,
+                _ => unreachable!(),
+            })
+            .collect();
+    }
+    (level.first().copied().unwrap_or_default(), proofs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn built_proofs_verify() {
+        let claims: Vec<_> = (1..=5u128)
+            .map(|i| (format!("addr{i}"), Uint128::new(i * 100)))
+            .collect();
+        let leaves: Vec<_> = claims.iter().map(|(address, amount)| leaf(address, *amount)).collect();
+        let (root, proofs) = build(&leaves);
+
+        for ((address, amount), proof) in claims.iter().zip(&proofs) {
+            let proof: Vec<_> = proof.iter().map(hex::encode).collect();
+            assert!(verify(&root, address, *amount, &proof).unwrap());
+            // The proof only holds for the exact amount
+            assert!(!verify(&root, address, *amount + Uint128::one(), &proof).unwrap());
+        }
+    }
+
+    #[test]
+    fn rejects_malformed_hashes() {
+        assert!(decode_hash("not hex").is_err());
+        assert!(decode_hash("abcd").is_err());
+        assert!(decode_hash(&hex::encode([7u8; 32])).is_ok());
+    }
+}
@@ -0,0 +1,134 @@
+//! Builds an airdrop tree from a CSV of `address,amount` lines and prints the root to register with
+//! `RegisterMerkleRoot`, the total amount to fund, and the proof each address submits with `ClaimAirdrop`.
+//!
+//! Usage: `cargo airdrop <claims.csv>`
+
+use std::{collections::BTreeSet, env, fs, process};
+
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{to_json_string, Uint128};
+
+use seints_row::airdrop;
+
+/// What a single address submits with `ClaimAirdrop`.
+#[cw_serde]
+struct Claim {
+    address: String,
+    amount: Uint128,
+    proof: Vec<String>,
+}
+
+/// Everything needed to register and claim the airdrop.
+#[cw_serde]
+struct Output {
+    root: String,
+    total_amount: Uint128,
+    claims: Vec<Claim>,
+}
+
+/// Parses `address,amount` lines, skipping blank lines and a header line. Fails on malformed lines, on an
+/// address listed twice, and when there are no claims at all.
+fn parse(csv: &str) -> Result<Vec<(String, Uint128)>, String> {
+    let mut claims = Vec::new();
+    let mut seen = BTreeSet::new();
+    for (number, line) in csv.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || (number == 0 && line.starts_with("address")) {
+            continue;
+        }
+        let (address, amount) = line
+            .split_once(',')
+            .ok_or_else(|| format!("line {}: expected `address,amount`", number + 1))?;
+        let address = address.trim();
+        if address.is_empty() {
+            return Err(format!("line {}: missing address", number + 1));
+        }
+        // A second leaf for the same address could never be claimed, since each address claims once
+        if !seen.insert(address) {
+            return Err(format!("line {}: duplicate address {address}", number + 1));
+        }
+        let amount = amount
+            .trim()
+            .parse::<u128>()
+            .map_err(|err| format!("line {}: invalid amount: {err}", number + 1))?;
+        claims.push((address.to_string(), Uint128::new(amount)));
+    }
+    if claims.is_empty() {
+        return Err("no claims".to_string());
+    }
+    Ok(claims)
+}
+
+fn run() -> Result<String, String> {
+    let path = env::args().nth(1).ok_or("usage: airdrop <claims.csv>")?;
+    let csv = fs::read_to_string(&path).map_err(|err| format!("cannot read {path}: {err}"))?;
+    let claims = parse(&csv).map_err(|err| format!("{path}: {err}"))?;
+
+    let leaves: Vec<_> = claims.iter().map(|(address, amount)| airdrop::leaf(address, *amount)).collect();
+    let (root, proofs) = airdrop::build(&leaves);
+    let total_amount = claims.iter().map(|(_, amount)| *amount).sum();
+    let output = Output {
+        root: hex::encode(root),
+        total_amount,
+        claims: claims
+            .into_iter()
+            .zip(proofs)
+            .map(|((address, amount), proof)| Claim {
+                address,
+                amount,
+                proof: proof.iter().map(hex::encode).collect(),
+            })
+            .collect(),
+    };
+    to_json_string(&output).map_err(|err| err.to_string())
+}
+
+fn main() {
+    match run() {
+        Ok(output) => println!("{output}"),
+        Err(err) => {
+            eprintln!("{err}");
+            process::exit(1);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_claims_after_a_header() {
+        let csv = "address,amount\n\nsei1alice, 1000\nsei1bob,2000\n";
+        let claims = parse(csv).unwrap();
+        assert_eq!(
+            vec![
+                ("sei1alice".to_string(), Uint128::new(1_000)),
+                ("sei1bob".to_string(), Uint128::new(2_000)),
+            ],
+            claims
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_lines() {
+        assert_eq!(Err("line 1: expected `address,amount`".to_string()), parse("sei1alice 1000"));
+        assert_eq!(Err("line 2: missing address".to_string()), parse("sei1alice,1\n,1000"));
+        let err = parse("sei1alice,lots").unwrap_err();
+        assert!(err.starts_with("line 1: invalid amount"), "{err}");
+        let err = parse("sei1alice,-5").unwrap_err();
+        assert!(err.starts_with("line 1: invalid amount"), "{err}");
+    }
+
+    #[test]
+    fn rejects_duplicate_addresses() {
+        let csv = "sei1alice,1000\nsei1bob,2000\n sei1alice ,3000";
+        assert_eq!(Err("line 3: duplicate address sei1alice".to_string()), parse(csv));
+    }
+
+    #[test]
+    fn rejects_empty_input() {
+        assert_eq!(Err("no claims".to_string()), parse(""));
+        assert_eq!(Err("no claims".to_string()), parse("address,amount\n\n"));
+    }
+}
@@ -1,11 +1,12 @@
 use cosmwasm_schema::write_api;
 
-use seint_row::msg::{ExecuteMsg, InstantiateMsg, QueryMsg};
+use seints_row::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
 
 fn main() {
     write_api! {
         instantiate: InstantiateMsg,
         execute: ExecuteMsg,
         query: QueryMsg,
+        migrate: MigrateMsg,
     }
 }
@@ -1,21 +1,55 @@
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    to_json_binary, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult, Uint128, Timestamp, Addr, Map,
+    to_json_binary, Binary, Deps, DepsMut, Env, MessageInfo, StdResult, Uint128, Addr, BlockInfo,
+    Order, StdError, Storage, Timestamp, Empty, Event, CosmosMsg, IbcMsg, IbcTimeout, BankMsg, Coin,
 };
-use cw2::set_contract_version;
-use url::Url;
+use cw2::{get_contract_version, set_contract_version};
+use cw_storage_plus::Bound;
+use cw_utils::{must_pay, Expiration};
+use crate::airdrop;
 use crate::error::ContractError;
+use crate::events;
+use crate::ibc::{self, Ics20Packet};
+use crate::permit;
+use crate::token_factory::{self, SeiMsg};
+use crate::helpers::Cw20ReceiveMsg;
+use crate::vesting::{self, Schedule};
 use crate::msg::{
-    ExecuteMsg, InstantiateMsg, QueryMsg, TokenInfoResponse, VestingInfoResponse, PoolReleaseInfoResponse,
-    MetadataResponse,
+    ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg, TokenInfoResponse, VestingInfoResponse, PoolReleaseInfoResponse,
+    MetadataResponse, AllowanceResponse, AllowanceInfo, AllAllowancesResponse, SpenderAllowanceInfo,
+    AllSpenderAllowancesResponse, BalanceResponse, MinterResponse, MarketingInfoResponse, LogoInfo,
+    DownloadLogoResponse, AllAccountsResponse, GetTokenInfoResponse, VestingAccount, VestingAccountsResponse,
+    ClaimableResponse, VestingProjectionResponse, KeeperInfoResponse, PendingOwnerResponse, HasRoleResponse,
+    RoleMembersResponse, PauseStatusResponse, FrozenAccount, FrozenAccountsResponse, FeeConfigResponse,
+    TotalSupplyResponse, VotingPowerResponse, DelegatesResponse, StakedResponse, PendingRewardsResponse,
+    UnbondingEntriesResponse, MerkleStageResponse, IsClaimedResponse, Permit, PermitAction, NonceResponse,
+    NativeDenomResponse, ChannelResponse, ListChannelsResponse,
 };
-use crate::state::{TokenInfo, TOKEN_INFO, BALANCES, VESTING, POOL_RELEASE_SCHEDULE, METADATA_URL, VestingInfo, PoolReleaseInfo};
+use crate::state::{
+    TokenInfo, TOKEN_INFO, BALANCES, VESTING, POOL_RELEASE_SCHEDULE, METADATA_URL, VestingInfo, Allowance, ALLOWANCES, ALLOWANCES_SPENDER, Config, CONFIG,
+    KEEPER_RESERVE, PendingOwner, PENDING_OWNER, Role, ROLES, MinterData,
+    Operation, PAUSE_STATE, FROZEN, FeeConfig, FEE_CONFIG, FEE_EXEMPT,
+    TOTAL_SUPPLY, DELEGATES, VOTES, Stake, StakingState, STAKING, STAKES, UnbondingEntry, UNBONDING,
+    MerkleStage, MERKLE_STAGES, AIRDROP_CLAIMS, DEFAULT_MAX_BATCH_SIZE,
+    NONCES, NATIVE_DENOM, NATIVE_ESCROW, CHANNELS, CHANNEL_ALLOWLIST, CHANNEL_ESCROW,
+};
+use semver::Version;
+use std::collections::BTreeSet;
 // Version info for migration
 const CONTRACT_NAME: &str = "crates.io:seints-token";
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
-const VESTING: Map<Addr, VestingInfo> = Map::new("vesting");
-const POOL_RELEASE_SCHEDULE: Map<Addr, PoolReleaseInfo> = Map::new("pool_release_schedule");
+// Highest transfer fee a fee manager can set, in basis points
+const MAX_FEE_BPS: u16 = 1_000;
+// Highest batch size limit the owner can set, so a full batch still fits in a block's gas limit
+const MAX_BATCH_SIZE_LIMIT: u32 = 500;
+// Name stored by the legacy layout, which only kept token info and balances
+const LEGACY_CONTRACT_NAME: &str = "crates.io:seints-row";
+
+/// Responses can carry Sei token factory messages to mint and burn the native denom. Every entry point shares this
+/// type, because a contract and its cw-multi-test wrapper have a single custom message type, so the contract is
+/// deliberately locked to Sei: chains that do not accept `SeiMsg` cannot run it.
+pub type Response = cosmwasm_std::Response<SeiMsg>;
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
@@ -26,30 +60,41 @@ pub fn instantiate(
 ) -> Result<Response, ContractError> {
     // Validate the token info
     if msg.decimals > 18 {
-        return Err(ContractError::InvalidDecimals {});
+        return Err(ContractError::InvalidDecimals { decimals: msg.decimals });
     }
-
-    // Ensure the initial supply is exactly 1 billion
-    let one_billion = Uint128::new(1_000_000_000);
-    if msg.initial_supply != one_billion {
-        return Err(ContractError::InvalidInitialSupply {
-            expected: one_billion,
-            actual: msg.initial_supply,
-        });
+    if msg.initial_supply.is_zero() {
+        return Err(ContractError::InvalidAmount { amount: msg.initial_supply });
     }
-
-    // Validate addresses
-    let team_address = deps.api.addr_validate(&msg.team_address)?;
-    let pool_address = deps.api.addr_validate(&msg.pool_address)?;
-
-    if team_address == pool_address {
-        return Err(ContractError::DuplicateAddresses {});
+    if !is_valid_url(&msg.metadata_url) {
+        return Err(ContractError::InvalidMetadata { url: msg.metadata_url });
     }
-
-    // Calculate distribution amounts
-    let team_amount = msg.initial_supply.multiply_ratio(20u128, 100u128); // 20%
-    let pool_amount = msg.initial_supply.multiply_ratio(50u128, 100u128); // 50%
-    let owner_amount = msg.initial_supply.multiply_ratio(30u128, 100u128); // 30%
+    let pool_address = msg
+        .pool_address
+        .as_deref()
+        .map(|pool| deps.api.addr_validate(pool))
+        .transpose()?;
+    let mint = match msg.mint {
+        Some(minter_info) => {
+            if let Some(schedule) = &minter_info.schedule {
+                schedule.validate()?;
+            }
+            if let Some(cap) = minter_info.cap {
+                if msg.initial_supply > cap {
+                    return Err(ContractError::MintLimitExceeded {
+                        required: msg.initial_supply,
+                        available: cap,
+                    });
+                }
+            }
+            Some(MinterData {
+                minter: deps.api.addr_validate(&minter_info.minter)?,
+                cap: minter_info.cap,
+                schedule: minter_info.schedule,
+                minted: Uint128::zero(),
+            })
+        }
+        None => None,
+    };
 
     // Save token info
     let token_info = TokenInfo {
@@ -57,60 +102,92 @@ pub fn instantiate(
         symbol: msg.symbol.clone(),
         decimals: msg.decimals,
         total_supply: msg.initial_supply,
-        owner: info.sender.clone(),
+        owner: Some(info.sender.clone()),
+        mint,
     };
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
     TOKEN_INFO.save(deps.storage, &token_info)?;
+    TOTAL_SUPPLY.save(deps.storage, &msg.initial_supply, env.block.height)?;
+    let treasury = msg
+        .treasury
+        .as_deref()
+        .map(|treasury| deps.api.addr_validate(treasury))
+        .transpose()?
+        .unwrap_or_else(|| info.sender.clone());
+    let config = Config {
+        pool: pool_address.clone(),
+        treasury,
+        keeper_tip: Uint128::zero(),
+        keeper_min_release: Uint128::zero(),
+        vesting_votes: msg.vesting_votes,
+        unbonding_period: msg.unbonding_period,
+        max_batch_size: DEFAULT_MAX_BATCH_SIZE,
+    };
+    CONFIG.save(deps.storage, &config)?;
+    METADATA_URL.save(deps.storage, &msg.metadata_url)?;
 
-    // Save the metadata URL
-    if !is_valid_url(&msg.metadata_url) {
-        return Err(ContractError::InvalidMetadataUrl {});
+    // The instantiator starts with every role and can hand them out from there
+    for role in Role::ALL {
+        ROLES.save(deps.storage, (role.as_str(), &info.sender), &Empty {})?;
     }
-    METADATA_URL.save(deps.storage, &msg.metadata_url)?;
 
-    // Mint 20% to the team
-    BALANCES.update(deps.storage, &team_address, |balance| -> StdResult<_> {
-        Ok(balance.unwrap_or_default() + team_amount)
-    })?;
+    // Distribute the initial supply according to the genesis allocations
+    let start_time = env.block.time;
+    let mut allocated = Uint128::zero();
+    let mut seen = BTreeSet::new();
+    for allocation in msg.allocations {
+        let address = deps.api.addr_validate(&allocation.address)?;
+        if !seen.insert(address.clone()) {
+            return Err(ContractError::DuplicateAddresses { address: address.into_string() });
+        }
 
-    // Mint 40% of the pool's tokens upfront
-    let pool_upfront_amount = pool_amount.multiply_ratio(40u128, 50u128); // 40% of 50%
-    BALANCES.update(deps.storage, &pool_address, |balance| -> StdResult<_> {
-        Ok(balance.unwrap_or_default() + pool_upfront_amount)
-    })?;
+        // Credit the immediately available part
+        if !allocation.amount.is_zero() {
+            credit(deps.storage, &address, allocation.amount, env.block.height)?;
+        }
+        allocated = allocated
+            .checked_add(allocation.amount)
+            .map_err(|_| ContractError::Overflow {})?;
 
-    // Lock 30% for the owner (vesting)
-    let start_time = env.block.time;
-    let release_schedule = vec![
-        (start_time.plus_years(1), owner_amount.multiply_ratio(10u128, 100u128)), // 10% after 1 year
-        (start_time.plus_years(2), owner_amount.multiply_ratio(10u128, 100u128)), // 10% after 2 years
-        (start_time.plus_years(3), owner_amount.multiply_ratio(10u128, 100u128)), // 10% after 3 years
-    ];
-    let vesting_info = VestingInfo {
-        amount: owner_amount,
-        start_time,
-        release_schedule,
-    };
-    VESTING.save(deps.storage, &info.sender, &vesting_info)?;
-
-    // Set up gradual release for the remaining 10% of the pool
-    let pool_gradual_amount = pool_amount.multiply_ratio(10u128, 50u128); // 10% of 50%
-    let pool_release_schedule = vec![
-        (start_time.plus_months(6), pool_gradual_amount.multiply_ratio(5u128, 10u128)), // 5% after 6 months
-        (start_time.plus_months(12), pool_gradual_amount.multiply_ratio(25u128, 100u128)), // 2.5% after 12 months
-        (start_time.plus_months(18), pool_gradual_amount.multiply_ratio(25u128, 100u128)), // 2.5% after 18 months
-    ];
-    let pool_release_info = PoolReleaseInfo {
-        amount: pool_gradual_amount,
-        release_schedule: pool_release_schedule,
-    };
-    POOL_RELEASE_SCHEDULE.save(deps.storage, &pool_address, &pool_release_info)?;
+        // Lock the vested part, either as a pool release or as regular vesting
+        if let Some(schedule) = allocation.vesting {
+            schedule.validate()?;
+            let locked = schedule.total();
+            allocated = allocated.checked_add(locked).map_err(|_| ContractError::Overflow {})?;
+            let revoker = allocation
+                .revoker
+                .as_deref()
+                .map(|revoker| deps.api.addr_validate(revoker))
+                .transpose()?;
+            let vesting_info = VestingInfo {
+                amount: locked,
+                released: Uint128::zero(),
+                start_time,
+                schedule,
+                revoker,
+            };
+            if pool_address.as_ref() == Some(&address) {
+                POOL_RELEASE_SCHEDULE.save(deps.storage, &address, &vesting_info)?;
+            } else {
+                VESTING.save(deps.storage, &address, &vesting_info)?;
+            }
+        }
+    }
+
+    // The allocations must account for the whole supply, no more and no less
+    if allocated != msg.initial_supply {
+        return Err(ContractError::InvalidInitialSupply {
+            expected: allocated,
+            actual: msg.initial_supply,
+        });
+    }
 
     Ok(Response::new()
-        .add_attribute("method", "instantiate")
-        .add_attribute("owner", info.sender)
-        .add_attribute("total_supply", msg.initial_supply)
-        .add_attribute("metadata_url", msg.metadata_url))
+        .add_event(events::Instantiated {
+            owner: info.sender,
+            total_supply: msg.initial_supply,
+            metadata_url: msg.metadata_url,
+        }))
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -121,11 +198,77 @@ pub fn execute(
     msg: ExecuteMsg,
 ) -> Result<Response, ContractError> {
     match msg {
-        ExecuteMsg::Transfer { recipient, amount } => execute::transfer(deps, info, recipient, amount),
-        ExecuteMsg::Burn { amount } => execute::burn(deps, info, amount),
+        ExecuteMsg::Transfer { recipient, amount } => execute::transfer(deps, env, info, recipient, amount),
+        ExecuteMsg::BatchTransfer { transfers } => execute::batch_transfer(deps, env, info, transfers),
+        ExecuteMsg::UpdateMaxBatchSize { max_batch_size } => execute::update_max_batch_size(deps, info, max_batch_size),
+        ExecuteMsg::Burn { amount } => execute::burn(deps, env, info, amount),
+        ExecuteMsg::Mint { recipient, amount } => execute::mint(deps, env, info, recipient, amount),
+        ExecuteMsg::UpdateMinter { new_minter } => execute::update_minter(deps, info, new_minter),
         ExecuteMsg::ReleaseVested {} => execute::release_vested(deps, env, info),
         ExecuteMsg::ReleasePool {} => execute::release_pool(deps, env, info),
+        ExecuteMsg::ReleaseFor { beneficiary } => execute::release_for(deps, env, info, beneficiary),
+        ExecuteMsg::ReleaseBatch { beneficiaries } => execute::release_batch(deps, env, info, beneficiaries),
+        ExecuteMsg::FundKeeperReserve { amount } => execute::fund_keeper_reserve(deps, env, info, amount),
+        ExecuteMsg::UpdateKeeperTip { tip, min_release } => execute::update_keeper_tip(deps, info, tip, min_release),
         ExecuteMsg::UpdateMetadata { metadata_url } => execute::update_metadata(deps, info, metadata_url),
+        ExecuteMsg::IncreaseAllowance { spender, amount, expires } => {
+            execute::increase_allowance(deps, env, info, spender, amount, expires)
+        }
+        ExecuteMsg::DecreaseAllowance { spender, amount, expires } => {
+            execute::decrease_allowance(deps, env, info, spender, amount, expires)
+        }
+        ExecuteMsg::TransferFrom { owner, recipient, amount } => {
+            execute::transfer_from(deps, env, info, owner, recipient, amount)
+        }
+        ExecuteMsg::BurnFrom { owner, amount } => execute::burn_from(deps, env, info, owner, amount),
+        ExecuteMsg::Send { contract, amount, msg } => execute::send(deps, env, info, contract, amount, msg),
+        ExecuteMsg::CreateVesting { beneficiary, schedule, revoker } => {
+            execute::create_vesting(deps, env, info, beneficiary, schedule, revoker)
+        }
+        ExecuteMsg::RevokeVesting { beneficiary } => execute::revoke_vesting(deps, env, info, beneficiary),
+        ExecuteMsg::ProposeNewOwner { new_owner, expiry } => {
+            execute::propose_new_owner(deps, env, info, new_owner, expiry)
+        }
+        ExecuteMsg::AcceptOwnership {} => execute::accept_ownership(deps, env, info),
+        ExecuteMsg::CancelOwnershipTransfer {} => execute::cancel_ownership_transfer(deps, info),
+        ExecuteMsg::RenounceOwnership {} => execute::renounce_ownership(deps, info),
+        ExecuteMsg::GrantRole { role, address } => execute::grant_role(deps, info, role, address),
+        ExecuteMsg::RevokeRole { role, address } => execute::revoke_role(deps, info, role, address),
+        ExecuteMsg::Pause { operations } => execute::set_paused(deps, info, operations, true),
+        ExecuteMsg::Unpause { operations } => execute::set_paused(deps, info, operations, false),
+        ExecuteMsg::FreezeAccount { address, reason } => execute::freeze_account(deps, info, address, reason),
+        ExecuteMsg::UnfreezeAccount { address } => execute::unfreeze_account(deps, info, address),
+        ExecuteMsg::UpdateFeeConfig { rate_bps, treasury_share_bps, burn_share_bps, pool_share_bps } => {
+            let fee_config = FeeConfig {
+                rate_bps,
+                treasury_share_bps,
+                burn_share_bps,
+                pool_share_bps,
+            };
+            execute::update_fee_config(deps, info, fee_config)
+        }
+        ExecuteMsg::SetFeeExempt { address, exempt } => execute::set_fee_exempt(deps, info, address, exempt),
+        ExecuteMsg::Delegate { delegatee } => execute::delegate(deps, env, info, delegatee),
+        ExecuteMsg::Stake { amount } => execute::stake(deps, env, info, amount),
+        ExecuteMsg::Unstake { amount } => execute::unstake(deps, env, info, amount),
+        ExecuteMsg::ClaimUnbonded {} => execute::claim_unbonded(deps, env, info),
+        ExecuteMsg::ClaimRewards {} => execute::claim_rewards(deps, env, info),
+        ExecuteMsg::FundRewards { amount, duration } => execute::fund_rewards(deps, env, info, amount, duration),
+        ExecuteMsg::RegisterMerkleRoot { stage, root, expiration, total_amount } => {
+            execute::register_merkle_root(deps, env, info, stage, root, expiration, total_amount)
+        }
+        ExecuteMsg::ClaimAirdrop { stage, amount, proof } => execute::claim_airdrop(deps, env, info, stage, amount, proof),
+        ExecuteMsg::ReclaimAirdrop { stage } => execute::reclaim_airdrop(deps, env, info, stage),
+        ExecuteMsg::IbcTransfer { channel, remote_address, amount, timeout } => {
+            execute::ibc_transfer(deps, env, info, channel, remote_address, amount, timeout)
+        }
+        ExecuteMsg::UpdateChannelAllowlist { channel_id, allowed } => {
+            execute::update_channel_allowlist(deps, info, channel_id, allowed)
+        }
+        ExecuteMsg::Permit { permit } => execute::permit(deps, env, info, permit),
+        ExecuteMsg::CreateNativeDenom { subdenom } => execute::create_native_denom(deps, env, info, subdenom),
+        ExecuteMsg::ConvertToNative { amount } => execute::convert_to_native(deps, env, info, amount),
+        ExecuteMsg::ConvertFromNative {} => execute::convert_from_native(deps, env, info),
     }
 }
 
@@ -134,60 +277,227 @@ pub mod execute {
 
     pub fn transfer(
         deps: DepsMut,
+        env: Env,
         info: MessageInfo,
         recipient: String,
         amount: Uint128,
     ) -> Result<Response, ContractError> {
+        ensure_not_paused(deps.storage, Operation::Transfers)?;
         let recipient_addr = deps.api.addr_validate(&recipient)?;
 
-        // Deduct tokens from sender first
-        BALANCES.update(deps.storage, &info.sender, |balance| -> StdResult<_> {
-            let balance = balance.unwrap_or_default();
-            if balance < amount {
-                return Err(StdError::generic_err("Insuficient balance"));
+        // Deduct tokens from sender first, then add them to the recipient
+        let fee = transfer_with_fee(deps.storage, &info.sender, &recipient_addr, amount, env.block.height)?;
+
+        Ok(Response::new()
+            .add_event(events::Transfer {
+                from: info.sender,
+                to: recipient_addr,
+                amount,
+                fee: fee.total,
+                fee_treasury: fee.treasury,
+                fee_burned: fee.burned,
+                fee_pool: fee.pool,
+            }))
+    }
+
+    pub fn batch_transfer(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        transfers: Vec<(String, Uint128)>,
+    ) -> Result<Response, ContractError> {
+        ensure_not_paused(deps.storage, Operation::Transfers)?;
+        if transfers.is_empty() {
+            return Err(ContractError::EmptyBatch {});
+        }
+        let max = CONFIG.load(deps.storage)?.max_batch_size;
+        if transfers.len() > max as usize {
+            return Err(ContractError::BatchTooLarge { size: transfers.len() as u64, max });
+        }
+
+        // Validate the whole batch before moving anything
+        let mut seen = BTreeSet::new();
+        let mut total = Uint128::zero();
+        let mut recipients = Vec::with_capacity(transfers.len());
+        for (recipient, amount) in transfers {
+            if amount.is_zero() {
+                return Err(ContractError::InvalidAmount { amount });
             }
-            Ok(balance - amount)
-        })?;
+            let recipient_addr = deps.api.addr_validate(&recipient)?;
+            if !seen.insert(recipient_addr.clone()) {
+                return Err(ContractError::DuplicateAddresses { address: recipient_addr.into_string() });
+            }
+            total = total.checked_add(amount).map_err(|_| ContractError::Overflow {})?;
+            recipients.push((recipient_addr, amount));
+        }
+
+        // Debit the sender once, then pay each recipient
+        let height = env.block.height;
+        debit(deps.storage, &info.sender, total, height)?;
+        let mut fee = TransferFee::default();
+        let mut transfer_events = Vec::with_capacity(recipients.len());
+        for (recipient_addr, amount) in recipients {
+            let transfer_fee = credit_with_fee(deps.storage, &info.sender, &recipient_addr, amount, height)?;
+            fee.accumulate(&transfer_fee);
+            transfer_events.push(events::Transfer {
+                from: info.sender.clone(),
+                to: recipient_addr,
+                amount,
+                fee: transfer_fee.total,
+                fee_treasury: transfer_fee.treasury,
+                fee_burned: transfer_fee.burned,
+                fee_pool: transfer_fee.pool,
+            });
+        }
+
+        Ok(Response::new()
+            .add_event(events::BatchTransfer {
+                from: info.sender,
+                recipients: transfer_events.len() as u64,
+                total,
+                fee: fee.total,
+                fee_treasury: fee.treasury,
+                fee_burned: fee.burned,
+                fee_pool: fee.pool,
+            })
+            .add_events(transfer_events.into_iter().map(Event::from)))
+    }
 
-        // Add tokens to recipient afterward
-        BALANCES.update(deps.storage, &recipient_addr, |balance| -> StdResult<_> {
-            Ok(balance.unwrap_or_default() + amount)
+    pub fn update_max_batch_size(
+        deps: DepsMut,
+        info: MessageInfo,
+        max_batch_size: u32,
+    ) -> Result<Response, ContractError> {
+        ensure_owner(deps.storage, &info.sender)?;
+        if max_batch_size == 0 || max_batch_size > MAX_BATCH_SIZE_LIMIT {
+            return Err(ContractError::InvalidMaxBatchSize { max_batch_size, limit: MAX_BATCH_SIZE_LIMIT });
+        }
+
+        CONFIG.update(deps.storage, |mut config| -> StdResult<_> {
+            config.max_batch_size = max_batch_size;
+            Ok(config)
         })?;
 
         Ok(Response::new()
-            .add_attribute("method", "transfer")
-            .add_attribute("from", info.sender)
-            .add_attribute("to", recipient)
-            .add_attribute("amount", amount))
+            .add_event(events::MaxBatchSizeUpdated { max_batch_size }))
+    }
+
+    pub fn send(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        contract: String,
+        amount: Uint128,
+        msg: Binary,
+    ) -> Result<Response, ContractError> {
+        if amount.is_zero() {
+            return Err(ContractError::InvalidAmount { amount });
+        }
+        ensure_not_paused(deps.storage, Operation::Transfers)?;
+        let contract_addr = deps.api.addr_validate(&contract)?;
+
+        // Move the tokens before notifying the receiving contract of what it actually received
+        let fee = transfer_with_fee(deps.storage, &info.sender, &contract_addr, amount, env.block.height)?;
+
+        let receive_msg = Cw20ReceiveMsg {
+            sender: info.sender.to_string(),
+            amount: amount - fee.total,
+            msg,
+        }
+        .into_cosmos_msg(contract_addr.clone())?;
+
+        Ok(Response::new()
+            .add_message(receive_msg)
+            .add_event(events::Send {
+                from: info.sender,
+                contract: contract_addr,
+                amount,
+                fee: fee.total,
+                fee_treasury: fee.treasury,
+                fee_burned: fee.burned,
+                fee_pool: fee.pool,
+            }))
     }
 
     pub fn burn(
         deps: DepsMut,
+        env: Env,
         info: MessageInfo,
         amount: Uint128,
     ) -> Result<Response, ContractError> {
-        // Deduct the tokens from the sender's balance
-        BALANCES.update(deps.storage, &info.sender, |balance| -> StdResult<_> {
-            let balance = balance.unwrap_or_default();
-            if balance < amount {
-                return Err(ContractError::InsufficientBalance {});
-            }
-            Ok(balance - amount)
-        })?;
+        ensure_not_paused(deps.storage, Operation::Burns)?;
+        // Deduct the tokens from the sender's balance and reduce the total supply
+        debit(deps.storage, &info.sender, amount, env.block.height)?;
+        reduce_total_supply(deps.storage, amount, env.block.height)?;
 
-        // Reduce the total supply
-        TOKEN_INFO.update(deps.storage, |mut token_info| -> StdResult<_> {
-            token_info.total_supply = token_info
+        Ok(Response::new()
+            .add_event(events::Burn { from: info.sender, amount }))
+    }
+
+    pub fn mint(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        recipient: String,
+        amount: Uint128,
+    ) -> Result<Response, ContractError> {
+        if amount.is_zero() {
+            return Err(ContractError::InvalidAmount { amount });
+        }
+        ensure_not_paused(deps.storage, Operation::Minting)?;
+        let mut token_info = TOKEN_INFO.load(deps.storage)?;
+        let available = mintable(&token_info, &env.block).unwrap_or(Uint128::MAX);
+        let mint = token_info.mint.as_mut().ok_or(ContractError::MintingDisabled {})?;
+        if info.sender != mint.minter {
+            return Err(ContractError::Unauthorized {});
+        }
+        if amount > available {
+            return Err(ContractError::MintLimitExceeded { required: amount, available });
+        }
+
+        // Track the minted amount separately, since burns lower the total supply but not the schedule
+        mint.minted = mint.minted.checked_add(amount).map_err(|_| ContractError::Overflow {})?;
+        token_info.total_supply = token_info
             .total_supply
-            .checked_sub(amount)
+            .checked_add(amount)
             .map_err(|_| ContractError::Overflow {})?;
-            Ok(token_info)  
-        })?;
+        TOKEN_INFO.save(deps.storage, &token_info)?;
+        TOTAL_SUPPLY.save(deps.storage, &token_info.total_supply, env.block.height)?;
+
+        let recipient_addr = deps.api.addr_validate(&recipient)?;
+        credit(deps.storage, &recipient_addr, amount, env.block.height)?;
 
         Ok(Response::new()
-            .add_attribute("method", "burn")
-            .add_attribute("from", info.sender)
-            .add_attribute("amount", amount))
+            .add_event(events::Mint {
+                minter: info.sender,
+                to: recipient_addr,
+                amount,
+            }))
+    }
+
+    pub fn update_minter(
+        deps: DepsMut,
+        info: MessageInfo,
+        new_minter: Option<String>,
+    ) -> Result<Response, ContractError> {
+        let new_minter_addr = new_minter.map(|minter| deps.api.addr_validate(&minter)).transpose()?;
+
+        // Once disabled, minting can never be turned back on
+        let mut token_info = TOKEN_INFO.load(deps.storage)?;
+        let mint = token_info.mint.as_mut().ok_or(ContractError::MintingDisabled {})?;
+        // Only the current minter can hand minting over or give it up
+        if info.sender != mint.minter {
+            return Err(ContractError::Unauthorized {});
+        }
+        match &new_minter_addr {
+            Some(new_minter) => mint.minter = new_minter.clone(),
+            None => token_info.mint = None,
+        }
+        TOKEN_INFO.save(deps.storage, &token_info)?;
+
+        Ok(Response::new().add_event(events::MinterUpdated {
+            new_minter: new_minter_addr.map(Addr::into_string).unwrap_or_else(|| "none".to_string()),
+        }))
     }
 
     pub fn release_vested(
@@ -195,69 +505,237 @@ pub mod execute {
         env: Env,
         info: MessageInfo,
     ) -> Result<Response, ContractError> {
-        let mut vesting_info = VESTING.load(deps.storage, &info.sender)?;
-        let mut total_released = Uint128::zero();
+        ensure_not_paused(deps.storage, Operation::VestingReleases)?;
+        // Release everything vested so far and credit it to the beneficiary
+        let released = vesting::release(deps.storage, &VESTING, &info.sender, &env.block)?;
+        credit(deps.storage, &info.sender, released, env.block.height)?;
+        unlock_vesting_weight(deps.storage, &info.sender, released, env.block.height)?;
+
+        Ok(Response::new()
+            .add_event(events::VestingReleased { beneficiary: info.sender, amount: released }))
+    }
+
+    pub fn release_pool(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+    ) -> Result<Response, ContractError> {
+        ensure_not_paused(deps.storage, Operation::PoolReleases)?;
+        // Pool releases follow the same vesting rules as owner vesting
+        let released = vesting::release(deps.storage, &POOL_RELEASE_SCHEDULE, &info.sender, &env.block)?;
+        credit(deps.storage, &info.sender, released, env.block.height)?;
+
+        Ok(Response::new()
+            .add_event(events::PoolReleased { beneficiary: info.sender, amount: released }))
+    }
+
+    pub fn release_for(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        beneficiary: String,
+    ) -> Result<Response, ContractError> {
+        let beneficiary_addr = deps.api.addr_validate(&beneficiary)?;
+        let released = release_on_behalf(deps.storage, &beneficiary_addr, &env.block)?;
+
+        let tipped_count = u128::from(earns_keeper_tip(deps.storage, released)?);
+        let tip = pay_keeper_tip(deps.storage, &info.sender, tipped_count, env.block.height)?;
+
+        Ok(Response::new()
+            .add_event(events::ReleasedFor {
+                keeper: info.sender,
+                beneficiary: beneficiary_addr,
+                amount: released,
+                tip,
+            }))
+    }
+
+    pub fn release_batch(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        beneficiaries: Vec<String>,
+    ) -> Result<Response, ContractError> {
+        if beneficiaries.is_empty() {
+            return Err(ContractError::EmptyBatch {});
+        }
+        let max = CONFIG.load(deps.storage)?.max_batch_size;
+        if beneficiaries.len() > max as usize {
+            return Err(ContractError::BatchTooLarge { size: beneficiaries.len() as u64, max });
+        }
+
+        // A repeated beneficiary would release nothing the second time but still count toward the batch
+        let mut seen = BTreeSet::new();
+        let mut beneficiary_addrs = Vec::with_capacity(beneficiaries.len());
+        for beneficiary in &beneficiaries {
+            let beneficiary_addr = deps.api.addr_validate(beneficiary)?;
+            if !seen.insert(beneficiary_addr.clone()) {
+                return Err(ContractError::DuplicateAddresses { address: beneficiary_addr.into_string() });
+            }
+            beneficiary_addrs.push(beneficiary_addr);
+        }
 
-        // Iterate through the release schedule
-        let mut last_processed_time = vesting_info.last_processed_time.unwrap_or(vesting_info.start_time);
-        for (timestamp, amount) in vesting_info.release_schedule.iter() {
-            if *timestamp > last_processed_time && env.block.time >= *timestamp {
-                total_released += *amount;
-                last_processed_time = *timestamp;
+        // Beneficiaries that cannot be released right now are reported instead of failing the whole batch
+        let mut skipped = vec![];
+        let mut total_released = Uint128::zero();
+        let mut released_count = 0u64;
+        let mut tipped_count = 0u128;
+        for beneficiary_addr in beneficiary_addrs {
+            if let Some(reason) = release_blocker(deps.storage, &beneficiary_addr)? {
+                skipped.push(events::ReleaseSkipped {
+                    keeper: info.sender.clone(),
+                    beneficiary: beneficiary_addr,
+                    reason: reason.to_string(),
+                });
+                continue;
+            }
+            let released = release_on_behalf(deps.storage, &beneficiary_addr, &env.block)?;
+            if !released.is_zero() {
+                released_count += 1;
+                total_released = total_released.checked_add(released).map_err(|_| ContractError::Overflow {})?;
             }
+            tipped_count += u128::from(earns_keeper_tip(deps.storage, released)?);
+        }
+        let tip = pay_keeper_tip(deps.storage, &info.sender, tipped_count, env.block.height)?;
+
+        Ok(Response::new()
+            .add_event(events::BatchReleased {
+                keeper: info.sender,
+                beneficiaries: beneficiaries.len() as u64,
+                released: released_count,
+                skipped: skipped.len() as u64,
+                amount: total_released,
+                tip,
+            })
+            .add_events(skipped.into_iter().map(Event::from)))
+    }
+
+    pub fn fund_keeper_reserve(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        amount: Uint128,
+    ) -> Result<Response, ContractError> {
+        if amount.is_zero() {
+            return Err(ContractError::InvalidAmount { amount });
         }
-        vesting_info.last_processed_time = Some(last_processed_time);
+        ensure_not_paused(deps.storage, Operation::Transfers)?;
 
-        // Remove released amounts from the schedule
-        vesting_info.release_schedule.retain(|(timestamp, _)| env.block.time < *timestamp);
+        // The reserve holds the tokens in escrow until they are paid out as tips
+        debit(deps.storage, &info.sender, amount, env.block.height)?;
+        let reserve = KEEPER_RESERVE.may_load(deps.storage)?.unwrap_or_default();
+        let reserve = reserve.checked_add(amount).map_err(|_| ContractError::Overflow {})?;
+        KEEPER_RESERVE.save(deps.storage, &reserve)?;
+
+        Ok(Response::new()
+            .add_event(events::KeeperReserveFunded {
+                from: info.sender,
+                amount,
+                reserve,
+            }))
+    }
 
-        // Update vesting info
-        vesting_info.amount -= total_released;
-        VESTING.save(deps.storage, &info.sender, &vesting_info)?;
+    pub fn update_keeper_tip(
+        deps: DepsMut,
+        info: MessageInfo,
+        tip: Uint128,
+        min_release: Uint128,
+    ) -> Result<Response, ContractError> {
+        ensure_owner(deps.storage, &info.sender)?;
 
-        // Transfer released tokens to the owner
-        BALANCES.update(deps.storage, &info.sender, |balance| -> StdResult<_> {
-            Ok(balance.unwrap_or_default() + total_released)
+        CONFIG.update(deps.storage, |mut config| -> StdResult<_> {
+            config.keeper_tip = tip;
+            config.keeper_min_release = min_release;
+            Ok(config)
         })?;
 
         Ok(Response::new()
-            .add_attribute("method", "release_vested")
-            .add_attribute("amount", total_released))
+            .add_event(events::KeeperTipUpdated { tip, min_release }))
     }
 
-    pub fn release_pool(
+    pub fn create_vesting(
         deps: DepsMut,
         env: Env,
         info: MessageInfo,
+        beneficiary: String,
+        schedule: Schedule,
+        revoker: Option<String>,
     ) -> Result<Response, ContractError> {
-        let mut pool_release_info = POOL_RELEASE_SCHEDULE.load(deps.storage, &info.sender)?;
-        let mut total_released = Uint128::zero();
+        ensure_not_paused(deps.storage, Operation::Transfers)?;
+        ensure_role(deps.storage, Role::VestingAdmin, &info.sender)?;
+        let beneficiary_addr = deps.api.addr_validate(&beneficiary)?;
+        let revoker = revoker.map(|revoker| deps.api.addr_validate(&revoker)).transpose()?;
+        schedule.validate()?;
 
-        // Iterate through the release schedule
-        let mut last_processed_time = pool_release_info.last_processed_time.unwrap_or(pool_release_info.start_time);
-        for (timestamp, amount) in pool_release_info.release_schedule.iter() {
-            if *timestamp > last_processed_time && env.block.time >= *timestamp {
-                total_released += *amount;
-                last_processed_time = *timestamp;
+        // A beneficiary holds a single record, which can only be replaced once fully released
+        if let Some(existing) = VESTING.may_load(deps.storage, &beneficiary_addr)? {
+            if existing.released < existing.amount {
+                return Err(ContractError::VestingExists { address: beneficiary_addr.into_string() });
             }
         }
-        pool_release_info.last_processed_time = Some(last_processed_time);
 
-        // Remove released amounts from the schedule
-        pool_release_info.release_schedule.retain(|(timestamp, _)| env.block.time < *timestamp);
+        // Escrow the tokens: they leave the sender's balance and stay locked in the vesting record
+        let amount = schedule.total();
+        debit(deps.storage, &info.sender, amount, env.block.height)?;
+        let vesting_info = VestingInfo {
+            amount,
+            released: Uint128::zero(),
+            start_time: env.block.time,
+            schedule,
+            revoker,
+        };
+        VESTING.save(deps.storage, &beneficiary_addr, &vesting_info)?;
+        if CONFIG.load(deps.storage)?.vesting_votes {
+            add_weight(deps.storage, &beneficiary_addr, amount, env.block.height)?;
+        }
 
-        // Update pool release info
-        pool_release_info.amount -= total_released;
-        POOL_RELEASE_SCHEDULE.save(deps.storage, &info.sender, &pool_release_info)?;
+        Ok(Response::new()
+            .add_event(events::VestingCreated {
+                creator: info.sender,
+                beneficiary: beneficiary_addr,
+                amount,
+            }))
+    }
 
-        // Transfer released tokens to the pool
-        BALANCES.update(deps.storage, &info.sender, |balance| -> StdResult<_> {
-            Ok(balance.unwrap_or_default() + total_released)
-        })?;
+    pub fn revoke_vesting(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        beneficiary: String,
+    ) -> Result<Response, ContractError> {
+        // Revoking pays out vested tokens, so it waits while vesting releases are paused
+        ensure_not_paused(deps.storage, Operation::VestingReleases)?;
+        let beneficiary_addr = deps.api.addr_validate(&beneficiary)?;
+        let mut vesting_info = VESTING.load(deps.storage, &beneficiary_addr)?;
+        match &vesting_info.revoker {
+            None => return Err(ContractError::NotRevocable { address: beneficiary_addr.into_string() }),
+            Some(revoker) if *revoker != info.sender => return Err(ContractError::Unauthorized {}),
+            Some(_) => {}
+        }
+
+        // Pay out what has vested, and claw back everything that has not
+        let vested = vesting_info.vested(&env.block);
+        let paid_out = vesting_info.releasable(&env.block);
+        let clawed_back = vesting_info.amount - vested;
+        let treasury = CONFIG.load(deps.storage)?.treasury;
+        credit_refund(deps.storage, &beneficiary_addr, paid_out, env.block.height)?;
+        credit_refund(deps.storage, &treasury, clawed_back, env.block.height)?;
+        unlock_vesting_weight(deps.storage, &beneficiary_addr, paid_out + clawed_back, env.block.height)?;
+
+        // Close the record: it is fully released and can no longer be revoked
+        vesting_info.amount = vested;
+        vesting_info.released = vested;
+        vesting_info.revoker = None;
+        VESTING.save(deps.storage, &beneficiary_addr, &vesting_info)?;
 
         Ok(Response::new()
-            .add_attribute("method", "release_pool")
-            .add_attribute("amount", total_released))
+            .add_event(events::VestingRevoked {
+                beneficiary: beneficiary_addr,
+                revoker: info.sender,
+                paid_out,
+                clawed_back,
+                treasury,
+            }))
     }
 
     pub fn update_metadata(
@@ -265,370 +743,4306 @@ pub mod execute {
         info: MessageInfo,
         metadata_url: String,
     ) -> Result<Response, ContractError> {
-        // Ensure only the owner can update the metadata
-        let token_info = TOKEN_INFO.load(deps.storage)?;
-        if info.sender != token_info.owner {
-            return Err(ContractError::Unauthorized {});
-        }
+        // Ensure only a metadata manager can update the metadata
+        ensure_role(deps.storage, Role::MetadataManager, &info.sender)?;
 
         // Validate the metadata URL format
         if !is_valid_url(&metadata_url) {
-            return Err(ContractError::InvalidMetadataUrl {});
+            return Err(ContractError::InvalidMetadata { url: metadata_url });
         }
 
         // Update the metadata URL
         METADATA_URL.save(deps.storage, &metadata_url)?;
 
         Ok(Response::new()
-            .add_attribute("method", "update_metadata")
-            .add_attribute("metadata_url", metadata_url))
+            .add_event(events::MetadataUpdated { metadata_url }))
     }
-}
 
-#[cfg_attr(not(feature = "library"), entry_point)]
-pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
-    match msg {
-        QueryMsg::GetTokenInfo {} => to_json_binary(&query::token_info(deps)?),
-        QueryMsg::GetBalance { address } => to_json_binary(&query::balance(deps, address)?),
-        QueryMsg::GetVestingInfo { address } => to_json_binary(&query::vesting_info(deps, address)?),
-        QueryMsg::GetPoolReleaseInfo { address } => to_json_binary(&query::pool_release_info(deps, address)?),
-        QueryMsg::GetMetadata {} => to_json_binary(&query::metadata(deps)?),
+    pub fn propose_new_owner(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        new_owner: String,
+        expiry: Option<Expiration>,
+    ) -> Result<Response, ContractError> {
+        ensure_owner(deps.storage, &info.sender)?;
+        let new_owner_addr = deps.api.addr_validate(&new_owner)?;
+        if expiry.is_some_and(|expiry| expiry.is_expired(&env.block)) {
+            return Err(ContractError::InvalidExpiration {});
+        }
+
+        // Nothing changes hands until the new owner accepts, so a wrong address can simply be replaced
+        let pending_owner = PendingOwner {
+            new_owner: new_owner_addr.clone(),
+            expiry,
+        };
+        PENDING_OWNER.save(deps.storage, &pending_owner)?;
+
+        Ok(Response::new()
+            .add_event(events::OwnershipProposed {
+                owner: info.sender,
+                pending_owner: new_owner_addr,
+            }))
     }
-}
 
-pub mod query {
-    use super::*;
+    pub fn accept_ownership(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+    ) -> Result<Response, ContractError> {
+        let pending_owner = PENDING_OWNER
+            .may_load(deps.storage)?
+            .ok_or(ContractError::NoPendingOwner {})?;
+        if info.sender != pending_owner.new_owner {
+            return Err(ContractError::Unauthorized {});
+        }
+        if pending_owner.expiry.is_some_and(|expiry| expiry.is_expired(&env.block)) {
+            return Err(ContractError::OwnershipTransferExpired {});
+        }
 
-    pub fn token_info(deps: Deps) -> StdResult<TokenInfoResponse> {
-        let token_info = TOKEN_INFO.load(deps.storage)?;
-        Ok(TokenInfoResponse {
-            name: token_info.name,
-            symbol: token_info.symbol,
-            decimals: token_info.decimals,
-            total_supply: token_info.total_supply,
-            owner: token_info.owner.to_string(),
-        })
+        TOKEN_INFO.update(deps.storage, |mut token_info| -> StdResult<_> {
+            token_info.owner = Some(info.sender.clone());
+            Ok(token_info)
+        })?;
+        PENDING_OWNER.remove(deps.storage);
+
+        Ok(Response::new()
+            .add_event(events::OwnershipAccepted { owner: info.sender }))
     }
 
-    pub fn balance(deps: Deps, address: String) -> StdResult<Uint128> {
-        let addr = deps.api.addr_validate(&address)?;
-        let balance = BALANCES.load(deps.storage, &addr).unwrap_or_default();
-        Ok(balance)
+    pub fn cancel_ownership_transfer(
+        deps: DepsMut,
+        info: MessageInfo,
+    ) -> Result<Response, ContractError> {
+        ensure_owner(deps.storage, &info.sender)?;
+        if !PENDING_OWNER.exists(deps.storage) {
+            return Err(ContractError::NoPendingOwner {});
+        }
+        PENDING_OWNER.remove(deps.storage);
+
+        Ok(Response::new()
+            .add_event(events::OwnershipTransferCancelled { owner: info.sender }))
     }
 
-    pub fn vesting_info(deps: Deps, address: String) -> StdResult<VestingInfoResponse> {
+    pub fn renounce_ownership(
+        deps: DepsMut,
+        info: MessageInfo,
+    ) -> Result<Response, ContractError> {
+        ensure_owner(deps.storage, &info.sender)?;
+
+        // A pending transfer must not resurrect ownership after it is renounced
+        TOKEN_INFO.update(deps.storage, |mut token_info| -> StdResult<_> {
+            token_info.owner = None;
+            Ok(token_info)
+        })?;
+        PENDING_OWNER.remove(deps.storage);
+
+        Ok(Response::new()
+            .add_event(events::OwnershipRenounced { owner: info.sender }))
+    }
+
+    pub fn grant_role(
+        deps: DepsMut,
+        info: MessageInfo,
+        role: Role,
+        address: String,
+    ) -> Result<Response, ContractError> {
+        ensure_owner(deps.storage, &info.sender)?;
         let addr = deps.api.addr_validate(&address)?;
-        let vesting_info = VESTING.load(deps.storage, &addr)?;
-        Ok(VestingInfoResponse {
-            amount: vesting_info.amount,
-            start_time: vesting_info.start_time.seconds(),
-            release_schedule: vesting_info
-                .release_schedule
-                .iter()
-                .map(|(t, a)| (t.seconds(), *a))
-                .collect(),
-        })
+        ROLES.save(deps.storage, (role.as_str(), &addr), &Empty {})?;
+
+        Ok(Response::new()
+            .add_event(events::RoleGranted { role: role.as_str().to_string(), address: addr }))
     }
 
-    pub fn pool_release_info(deps: Deps, address: String) -> StdResult<PoolReleaseInfoResponse> {
+    pub fn revoke_role(
+        deps: DepsMut,
+        info: MessageInfo,
+        role: Role,
+        address: String,
+    ) -> Result<Response, ContractError> {
+        ensure_owner(deps.storage, &info.sender)?;
         let addr = deps.api.addr_validate(&address)?;
-        let pool_release_info = POOL_RELEASE_SCHEDULE.load(deps.storage, &addr)?;
-        Ok(PoolReleaseInfoResponse {
-            amount: pool_release_info.amount,
-            release_schedule: pool_release_info
-                .release_schedule
-                .iter()
-                .map(|(t, a)| (t.seconds(), *a))
-                .collect(),
-        })
+        ROLES.remove(deps.storage, (role.as_str(), &addr));
+
+        Ok(Response::new()
+            .add_event(events::RoleRevoked { role: role.as_str().to_string(), address: addr }))
+    }
+
+    pub fn set_paused(
+        deps: DepsMut,
+        info: MessageInfo,
+        operations: Option<Vec<Operation>>,
+        paused: bool,
+    ) -> Result<Response, ContractError> {
+        ensure_role(deps.storage, Role::Pauser, &info.sender)?;
+
+        let operations = operations.unwrap_or_else(|| Operation::ALL.to_vec());
+        let mut pause_state = PAUSE_STATE.may_load(deps.storage)?.unwrap_or_default();
+        for operation in &operations {
+            *pause_state.switch(*operation) = paused;
+        }
+        PAUSE_STATE.save(deps.storage, &pause_state)?;
+
+        let operations = operations.iter().map(Operation::as_str).collect::<Vec<_>>().join(",");
+        let event: Event = if paused {
+            events::Paused { operations, by: info.sender }.into()
+        } else {
+            events::Unpaused { operations, by: info.sender }.into()
+        };
+        Ok(Response::new().add_event(event))
+    }
+
+    pub fn freeze_account(
+        deps: DepsMut,
+        info: MessageInfo,
+        address: String,
+        reason: String,
+    ) -> Result<Response, ContractError> {
+        ensure_role(deps.storage, Role::Compliance, &info.sender)?;
+        let addr = deps.api.addr_validate(&address)?;
+        FROZEN.save(deps.storage, &addr, &reason)?;
+
+        Ok(Response::new()
+            .add_event(events::AccountFrozen {
+                address: addr,
+                reason,
+                by: info.sender,
+            }))
+    }
+
+    pub fn unfreeze_account(
+        deps: DepsMut,
+        info: MessageInfo,
+        address: String,
+    ) -> Result<Response, ContractError> {
+        ensure_role(deps.storage, Role::Compliance, &info.sender)?;
+        let addr = deps.api.addr_validate(&address)?;
+        FROZEN.remove(deps.storage, &addr);
+
+        Ok(Response::new()
+            .add_event(events::AccountUnfrozen { address: addr, by: info.sender }))
+    }
+
+    pub fn update_fee_config(
+        deps: DepsMut,
+        info: MessageInfo,
+        fee_config: FeeConfig,
+    ) -> Result<Response, ContractError> {
+        ensure_role(deps.storage, Role::FeeManager, &info.sender)?;
+
+        let shares = u32::from(fee_config.treasury_share_bps)
+            + u32::from(fee_config.burn_share_bps)
+            + u32::from(fee_config.pool_share_bps);
+        let has_pool = CONFIG.load(deps.storage)?.pool.is_some();
+        if fee_config.rate_bps > MAX_FEE_BPS || shares != 10_000 || (fee_config.pool_share_bps > 0 && !has_pool) {
+            return Err(ContractError::InvalidFeeConfig {});
+        }
+        FEE_CONFIG.save(deps.storage, &fee_config)?;
+
+        Ok(Response::new()
+            .add_event(events::FeeConfigUpdated {
+                rate_bps: fee_config.rate_bps,
+                treasury_share_bps: fee_config.treasury_share_bps,
+                burn_share_bps: fee_config.burn_share_bps,
+                pool_share_bps: fee_config.pool_share_bps,
+            }))
+    }
+
+    pub fn set_fee_exempt(
+        deps: DepsMut,
+        info: MessageInfo,
+        address: String,
+        exempt: bool,
+    ) -> Result<Response, ContractError> {
+        ensure_role(deps.storage, Role::FeeManager, &info.sender)?;
+        let addr = deps.api.addr_validate(&address)?;
+        if exempt {
+            FEE_EXEMPT.save(deps.storage, &addr, &Empty {})?;
+        } else {
+            FEE_EXEMPT.remove(deps.storage, &addr);
+        }
+
+        Ok(Response::new()
+            .add_event(events::FeeExemptionUpdated { address: addr, exempt }))
+    }
+
+    pub fn delegate(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        delegatee: String,
+    ) -> Result<Response, ContractError> {
+        let delegatee_addr = deps.api.addr_validate(&delegatee)?;
+        let height = env.block.height;
+
+        // Move the sender's whole weight from its previous delegatee to the new one
+        let weight = voting_weight(deps.storage, &info.sender)?;
+        if let Some(previous) = DELEGATES.may_load(deps.storage, &info.sender)? {
+            VOTES.update(deps.storage, &previous, height, |votes| -> StdResult<_> {
+                Ok(votes.unwrap_or_default().checked_sub(weight)?)
+            })?;
+        }
+        DELEGATES.save(deps.storage, &info.sender, &delegatee_addr)?;
+        VOTES.update(deps.storage, &delegatee_addr, height, |votes| -> StdResult<_> {
+            Ok(votes.unwrap_or_default().checked_add(weight)?)
+        })?;
+
+        Ok(Response::new()
+            .add_event(events::Delegated {
+                delegator: info.sender,
+                delegatee: delegatee_addr,
+                weight,
+            }))
+    }
+
+    pub fn stake(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        amount: Uint128,
+    ) -> Result<Response, ContractError> {
+        if amount.is_zero() {
+            return Err(ContractError::InvalidAmount { amount });
+        }
+        ensure_not_paused(deps.storage, Operation::Transfers)?;
+
+        let (mut staking, mut stake) = update_rewards(deps.storage, &info.sender, env.block.time.seconds())?;
+        debit(deps.storage, &info.sender, amount, env.block.height)?;
+        stake.amount = stake.amount.checked_add(amount).map_err(|_| ContractError::Overflow {})?;
+        staking.total_staked = staking.total_staked.checked_add(amount).map_err(|_| ContractError::Overflow {})?;
+        STAKES.save(deps.storage, &info.sender, &stake)?;
+        STAKING.save(deps.storage, &staking)?;
+
+        Ok(Response::new()
+            .add_event(events::Staked {
+                staker: info.sender,
+                amount,
+                staked: stake.amount,
+            }))
+    }
+
+    pub fn unstake(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        amount: Uint128,
+    ) -> Result<Response, ContractError> {
+        if amount.is_zero() {
+            return Err(ContractError::InvalidAmount { amount });
+        }
+        ensure_not_paused(deps.storage, Operation::Transfers)?;
+
+        let (mut staking, mut stake) = update_rewards(deps.storage, &info.sender, env.block.time.seconds())?;
+        stake.amount = stake
+            .amount
+            .checked_sub(amount)
+            .map_err(|_| ContractError::InsufficientBalance { required: amount, available: stake.amount })?;
+        staking.total_staked -= amount;
+        STAKES.save(deps.storage, &info.sender, &stake)?;
+        STAKING.save(deps.storage, &staking)?;
+
+        // The tokens stay out of the balance until the unbonding period has passed
+        let release_at = env.block.time.plus_seconds(CONFIG.load(deps.storage)?.unbonding_period);
+        let mut entries = UNBONDING.may_load(deps.storage, &info.sender)?.unwrap_or_default();
+        entries.push(UnbondingEntry { amount, release_at });
+        UNBONDING.save(deps.storage, &info.sender, &entries)?;
+
+        Ok(Response::new()
+            .add_event(events::Unstaked {
+                staker: info.sender,
+                amount,
+                release_at: release_at.seconds(),
+            }))
+    }
+
+    pub fn claim_unbonded(deps: DepsMut, env: Env, info: MessageInfo) -> Result<Response, ContractError> {
+        ensure_not_paused(deps.storage, Operation::Transfers)?;
+        let entries = UNBONDING.may_load(deps.storage, &info.sender)?.unwrap_or_default();
+        let (matured, unbonding): (Vec<_>, Vec<_>) =
+            entries.into_iter().partition(|entry| entry.release_at <= env.block.time);
+        let amount: Uint128 = matured.iter().map(|entry| entry.amount).sum();
+        if amount.is_zero() {
+            return Err(ContractError::NothingToClaim {});
+        }
+
+        if unbonding.is_empty() {
+            UNBONDING.remove(deps.storage, &info.sender);
+        } else {
+            UNBONDING.save(deps.storage, &info.sender, &unbonding)?;
+        }
+        credit(deps.storage, &info.sender, amount, env.block.height)?;
+
+        Ok(Response::new()
+            .add_event(events::UnbondedClaimed { staker: info.sender, amount }))
+    }
+
+    pub fn claim_rewards(deps: DepsMut, env: Env, info: MessageInfo) -> Result<Response, ContractError> {
+        ensure_not_paused(deps.storage, Operation::Transfers)?;
+        let (staking, mut stake) = update_rewards(deps.storage, &info.sender, env.block.time.seconds())?;
+        let rewards = stake.pending_rewards;
+        if rewards.is_zero() {
+            return Err(ContractError::NothingToClaim {});
+        }
+
+        stake.pending_rewards = Uint128::zero();
+        STAKES.save(deps.storage, &info.sender, &stake)?;
+        STAKING.save(deps.storage, &staking)?;
+        credit(deps.storage, &info.sender, rewards, env.block.height)?;
+
+        Ok(Response::new()
+            .add_event(events::RewardsClaimed { staker: info.sender, amount: rewards }))
+    }
+
+    pub fn fund_rewards(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        amount: Uint128,
+        duration: u64,
+    ) -> Result<Response, ContractError> {
+        let pool = ensure_pool_operator(deps.storage, &info.sender)?;
+        if amount.is_zero() {
+            return Err(ContractError::InvalidAmount { amount });
+        }
+        ensure_not_paused(deps.storage, Operation::Transfers)?;
+
+        // Rewards are funded from the pool allocation and held by the contract until claimed
+        debit(deps.storage, &pool, amount, env.block.height)?;
+        let now = env.block.time.seconds();
+        let mut staking = STAKING.may_load(deps.storage)?.unwrap_or_default();
+        staking.accrue(now);
+        let total = amount
+            .checked_add(staking.remaining_rewards(now))
+            .map_err(|_| ContractError::Overflow {})?;
+        let reward_rate = total.checked_div(Uint128::from(duration)).unwrap_or_default();
+        if reward_rate.is_zero() {
+            return Err(ContractError::InvalidRewardPeriod { amount, duration });
+        }
+        staking.reward_rate = reward_rate;
+        staking.last_update = now;
+        staking.period_finish = now + duration;
+        STAKING.save(deps.storage, &staking)?;
+
+        Ok(Response::new()
+            .add_event(events::RewardsFunded {
+                funder: pool,
+                amount,
+                reward_rate,
+                period_finish: staking.period_finish,
+            }))
+    }
+
+    pub fn register_merkle_root(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        stage: u8,
+        root: String,
+        expiration: Expiration,
+        total_amount: Uint128,
+    ) -> Result<Response, ContractError> {
+        let pool = ensure_pool_operator(deps.storage, &info.sender)?;
+        if total_amount.is_zero() {
+            return Err(ContractError::InvalidAmount { amount: total_amount });
+        }
+        if expiration.is_expired(&env.block) {
+            return Err(ContractError::InvalidExpiration {});
+        }
+        if MERKLE_STAGES.has(deps.storage, stage) {
+            return Err(ContractError::StageExists { stage });
+        }
+        airdrop::decode_hash(&root)?;
+        ensure_not_paused(deps.storage, Operation::Transfers)?;
+
+        // The stage holds the tokens in escrow until they are claimed or reclaimed
+        debit(deps.storage, &pool, total_amount, env.block.height)?;
+        let merkle_stage = MerkleStage {
+            root: root.clone(),
+            expiration,
+            total_amount,
+            claimed_amount: Uint128::zero(),
+            funder: pool,
+            reclaimed: false,
+        };
+        MERKLE_STAGES.save(deps.storage, stage, &merkle_stage)?;
+
+        Ok(Response::new()
+            .add_event(events::AirdropRegistered {
+                stage,
+                root,
+                total_amount,
+            }))
+    }
+
+    pub fn claim_airdrop(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        stage: u8,
+        amount: Uint128,
+        proof: Vec<String>,
+    ) -> Result<Response, ContractError> {
+        let mut merkle_stage = MERKLE_STAGES.load(deps.storage, stage)?;
+        if merkle_stage.expiration.is_expired(&env.block) {
+            return Err(ContractError::StageExpired { stage });
+        }
+        if AIRDROP_CLAIMS.has(deps.storage, (stage, &info.sender)) {
+            return Err(ContractError::AlreadyClaimed { stage, address: info.sender.into_string() });
+        }
+        let root = airdrop::decode_hash(&merkle_stage.root)?;
+        if !airdrop::verify(&root, info.sender.as_str(), amount, &proof)? {
+            return Err(ContractError::InvalidProof {});
+        }
+        ensure_not_paused(deps.storage, Operation::Transfers)?;
+
+        // A tree that promises more than was funded cannot pay out beyond the escrow
+        let available = merkle_stage.total_amount - merkle_stage.claimed_amount;
+        if amount > available {
+            return Err(ContractError::InsufficientBalance { required: amount, available });
+        }
+        merkle_stage.claimed_amount += amount;
+        MERKLE_STAGES.save(deps.storage, stage, &merkle_stage)?;
+        AIRDROP_CLAIMS.save(deps.storage, (stage, &info.sender), &Empty {})?;
+        credit(deps.storage, &info.sender, amount, env.block.height)?;
+
+        Ok(Response::new()
+            .add_event(events::AirdropClaimed {
+                stage,
+                address: info.sender,
+                amount,
+            }))
+    }
+
+    pub fn reclaim_airdrop(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        stage: u8,
+    ) -> Result<Response, ContractError> {
+        ensure_owner(deps.storage, &info.sender)?;
+        let mut merkle_stage = MERKLE_STAGES.load(deps.storage, stage)?;
+        if !merkle_stage.expiration.is_expired(&env.block) {
+            return Err(ContractError::StageNotExpired { stage });
+        }
+        let unclaimed = merkle_stage.total_amount - merkle_stage.claimed_amount;
+        if merkle_stage.reclaimed || unclaimed.is_zero() {
+            return Err(ContractError::NothingToClaim {});
+        }
+
+        merkle_stage.reclaimed = true;
+        MERKLE_STAGES.save(deps.storage, stage, &merkle_stage)?;
+        credit(deps.storage, &merkle_stage.funder, unclaimed, env.block.height)?;
+
+        Ok(Response::new()
+            .add_event(events::AirdropReclaimed {
+                stage,
+                recipient: merkle_stage.funder,
+                amount: unclaimed,
+            }))
+    }
+
+    pub fn create_native_denom(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        subdenom: String,
+    ) -> Result<Response, ContractError> {
+        ensure_owner(deps.storage, &info.sender)?;
+        if let Some(denom) = NATIVE_DENOM.may_load(deps.storage)? {
+            return Err(ContractError::NativeDenomExists { denom });
+        }
+
+        let denom = token_factory::full_denom(&env.contract.address, &subdenom);
+        NATIVE_DENOM.save(deps.storage, &denom)?;
+        NATIVE_ESCROW.save(deps.storage, &Uint128::zero())?;
+
+        Ok(Response::new()
+            .add_message(CosmosMsg::Custom(SeiMsg::CreateDenom { subdenom }))
+            .add_event(events::NativeDenomCreated { denom }))
+    }
+
+    pub fn convert_to_native(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        amount: Uint128,
+    ) -> Result<Response, ContractError> {
+        if amount.is_zero() {
+            return Err(ContractError::InvalidAmount { amount });
+        }
+        ensure_not_paused(deps.storage, Operation::Transfers)?;
+        let denom = NATIVE_DENOM.may_load(deps.storage)?.ok_or(ContractError::NativeDenomNotSet {})?;
+
+        // Escrow the tokens, then mint the same amount of the native denom against them
+        debit(deps.storage, &info.sender, amount, env.block.height)?;
+        let escrowed = NATIVE_ESCROW.load(deps.storage)?.checked_add(amount).map_err(|_| ContractError::Overflow {})?;
+        let total_supply = TOKEN_INFO.load(deps.storage)?.total_supply;
+        if escrowed > total_supply {
+            return Err(ContractError::SupplyInvariant { escrowed, total_supply });
+        }
+        NATIVE_ESCROW.save(deps.storage, &escrowed)?;
+
+        // The token factory mints to the contract, which passes the coins on
+        let coins = vec![Coin::new(amount, &denom)];
+        Ok(Response::new()
+            .add_message(CosmosMsg::Custom(SeiMsg::MintTokens { amount: coins[0].clone() }))
+            .add_message(BankMsg::Send { to_address: info.sender.to_string(), amount: coins })
+            .add_event(events::ConvertedToNative {
+                address: info.sender,
+                denom,
+                amount,
+            }))
+    }
+
+    pub fn convert_from_native(deps: DepsMut, env: Env, info: MessageInfo) -> Result<Response, ContractError> {
+        ensure_not_paused(deps.storage, Operation::Transfers)?;
+        let denom = NATIVE_DENOM.may_load(deps.storage)?.ok_or(ContractError::NativeDenomNotSet {})?;
+        let amount = must_pay(&info, &denom)?;
+
+        // Every native token in circulation is backed by escrow, so this only fails if the invariant broke
+        let escrowed = NATIVE_ESCROW.load(deps.storage)?;
+        if amount > escrowed {
+            let total_supply = TOKEN_INFO.load(deps.storage)?.total_supply;
+            return Err(ContractError::SupplyInvariant { escrowed, total_supply });
+        }
+        NATIVE_ESCROW.save(deps.storage, &(escrowed - amount))?;
+        credit(deps.storage, &info.sender, amount, env.block.height)?;
+
+        // The attached coins are now held by the contract, which burns them from its own balance
+        let burn = SeiMsg::BurnTokens { amount: Coin::new(amount, &denom) };
+        Ok(Response::new()
+            .add_message(CosmosMsg::Custom(burn))
+            .add_event(events::ConvertedFromNative {
+                address: info.sender,
+                denom,
+                amount,
+            }))
+    }
+
+    pub fn ibc_transfer(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        channel: String,
+        remote_address: String,
+        amount: Uint128,
+        timeout: Option<u64>,
+    ) -> Result<Response, ContractError> {
+        if amount.is_zero() {
+            return Err(ContractError::InvalidAmount { amount });
+        }
+        ensure_not_paused(deps.storage, Operation::Transfers)?;
+        if !CHANNELS.has(deps.storage, &channel) {
+            return Err(ContractError::UnknownChannel { channel });
+        }
+        if !CHANNEL_ALLOWLIST.has(deps.storage, &channel) {
+            return Err(ContractError::ChannelNotAllowed { channel });
+        }
+
+        // The channel holds the tokens in escrow until they return or are refunded
+        debit(deps.storage, &info.sender, amount, env.block.height)?;
+        CHANNEL_ESCROW.update(deps.storage, &channel, |escrowed| -> Result<_, ContractError> {
+            escrowed.unwrap_or_default().checked_add(amount).map_err(|_| ContractError::Overflow {})
+        })?;
+
+        let packet = Ics20Packet {
+            amount,
+            denom: ibc::denom(&env.contract.address),
+            receiver: remote_address.clone(),
+            sender: info.sender.to_string(),
+            memo: None,
+        };
+        let timeout = env.block.time.plus_seconds(timeout.unwrap_or(ibc::DEFAULT_TIMEOUT_SECONDS));
+        let send = IbcMsg::SendPacket {
+            channel_id: channel.clone(),
+            data: to_json_binary(&packet)?,
+            timeout: IbcTimeout::with_timestamp(timeout),
+        };
+
+        Ok(Response::new()
+            .add_message(send)
+            .add_event(events::IbcTransfer {
+                from: info.sender,
+                channel,
+                to: remote_address,
+                amount,
+            }))
+    }
+
+    pub fn update_channel_allowlist(
+        deps: DepsMut,
+        info: MessageInfo,
+        channel_id: String,
+        allowed: bool,
+    ) -> Result<Response, ContractError> {
+        ensure_owner(deps.storage, &info.sender)?;
+
+        if allowed {
+            CHANNEL_ALLOWLIST.save(deps.storage, &channel_id, &Empty {})?;
+        } else {
+            CHANNEL_ALLOWLIST.remove(deps.storage, &channel_id);
+        }
+
+        Ok(Response::new()
+            .add_event(events::ChannelAllowlistUpdated { channel: channel_id, allowed }))
+    }
+
+    pub fn permit(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        permit: Permit,
+    ) -> Result<Response, ContractError> {
+        let params = permit.params;
+        if params.chain_id != env.block.chain_id || params.contract != env.contract.address.as_str() {
+            return Err(ContractError::WrongPermitDomain {
+                chain_id: params.chain_id,
+                contract: params.contract,
+            });
+        }
+        if params.expires.is_expired(&env.block) {
+            return Err(ContractError::PermitExpired {});
+        }
+
+        // The key must belong to the owner and must have signed exactly these params
+        let owner = deps.api.addr_validate(&params.owner)?;
+        if deps.api.addr_canonicalize(owner.as_str())? != permit::pubkey_to_canonical(&permit.pubkey) {
+            return Err(ContractError::InvalidSignature {});
+        }
+        let digest = permit::digest(&params)?;
+        let verified = deps
+            .api
+            .secp256k1_verify(&digest, &permit.signature, &permit.pubkey)
+            .map_err(|_| ContractError::InvalidSignature {})?;
+        if !verified {
+            return Err(ContractError::InvalidSignature {});
+        }
+
+        let expected = NONCES.may_load(deps.storage, &owner)?.unwrap_or_default();
+        if params.nonce != expected {
+            return Err(ContractError::InvalidNonce { expected, actual: params.nonce });
+        }
+        NONCES.save(deps.storage, &owner, &(expected + 1))?;
+
+        // Perform the action exactly as if the owner had sent it
+        let owner_info = MessageInfo { sender: owner.clone(), funds: vec![] };
+        let response = match params.action {
+            PermitAction::IncreaseAllowance { spender, amount, expires } => {
+                increase_allowance(deps, env, owner_info, spender, amount, expires)?
+            }
+            PermitAction::Transfer { recipient, amount } => transfer(deps, env, owner_info, recipient, amount)?,
+        };
+
+        Ok(response.add_event(events::PermitUsed {
+            owner,
+            nonce: params.nonce,
+            relayer: info.sender,
+        }))
+    }
+
+    pub fn increase_allowance(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        spender: String,
+        amount: Uint128,
+        expires: Option<Expiration>,
+    ) -> Result<Response, ContractError> {
+        let spender_addr = deps.api.addr_validate(&spender)?;
+        if spender_addr == info.sender {
+            return Err(ContractError::CannotSetOwnAccount {});
+        }
+
+        let allowance = ALLOWANCES.update(
+            deps.storage,
+            (&info.sender, &spender_addr),
+            |allowance| -> Result<_, ContractError> {
+                let mut allowance = allowance.unwrap_or_default();
+                if let Some(expires) = expires {
+                    if expires.is_expired(&env.block) {
+                        return Err(ContractError::InvalidExpiration {});
+                    }
+                    allowance.expires = expires;
+                }
+                allowance.allowance = allowance
+                    .allowance
+                    .checked_add(amount)
+                    .map_err(|_| ContractError::Overflow {})?;
+                Ok(allowance)
+            },
+        )?;
+        ALLOWANCES_SPENDER.save(deps.storage, (&spender_addr, &info.sender), &allowance)?;
+
+        Ok(Response::new()
+            .add_event(events::AllowanceIncreased {
+                owner: info.sender,
+                spender: spender_addr,
+                amount,
+            }))
+    }
+
+    pub fn decrease_allowance(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        spender: String,
+        amount: Uint128,
+        expires: Option<Expiration>,
+    ) -> Result<Response, ContractError> {
+        let spender_addr = deps.api.addr_validate(&spender)?;
+        if spender_addr == info.sender {
+            return Err(ContractError::CannotSetOwnAccount {});
+        }
+
+        let key = (&info.sender, &spender_addr);
+        let mut allowance = ALLOWANCES
+            .may_load(deps.storage, key)?
+            .ok_or(ContractError::NoAllowance {})?;
+
+        if amount < allowance.allowance {
+            // Lower the allowance and optionally move its expiration
+            allowance.allowance -= amount;
+            if let Some(expires) = expires {
+                if expires.is_expired(&env.block) {
+                    return Err(ContractError::InvalidExpiration {});
+                }
+                allowance.expires = expires;
+            }
+            ALLOWANCES.save(deps.storage, key, &allowance)?;
+            ALLOWANCES_SPENDER.save(deps.storage, (&spender_addr, &info.sender), &allowance)?;
+        } else {
+            // The allowance would drop to zero, so remove it entirely
+            ALLOWANCES.remove(deps.storage, key);
+            ALLOWANCES_SPENDER.remove(deps.storage, (&spender_addr, &info.sender));
+        }
+
+        Ok(Response::new()
+            .add_event(events::AllowanceDecreased {
+                owner: info.sender,
+                spender: spender_addr,
+                amount,
+            }))
+    }
+
+    pub fn transfer_from(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        owner: String,
+        recipient: String,
+        amount: Uint128,
+    ) -> Result<Response, ContractError> {
+        if amount.is_zero() {
+            return Err(ContractError::InvalidAmount { amount });
+        }
+        ensure_not_paused(deps.storage, Operation::Transfers)?;
+        let owner_addr = deps.api.addr_validate(&owner)?;
+        let recipient_addr = deps.api.addr_validate(&recipient)?;
+
+        // Spend the allowance before moving any tokens
+        deduct_allowance(deps.storage, &owner_addr, &info.sender, &env.block, amount)?;
+        let fee = transfer_with_fee(deps.storage, &owner_addr, &recipient_addr, amount, env.block.height)?;
+
+        Ok(Response::new()
+            .add_event(events::TransferFrom {
+                owner: owner_addr,
+                spender: info.sender,
+                to: recipient_addr,
+                amount,
+                fee: fee.total,
+                fee_treasury: fee.treasury,
+                fee_burned: fee.burned,
+                fee_pool: fee.pool,
+            }))
+    }
+
+    pub fn burn_from(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        owner: String,
+        amount: Uint128,
+    ) -> Result<Response, ContractError> {
+        if amount.is_zero() {
+            return Err(ContractError::InvalidAmount { amount });
+        }
+        ensure_not_paused(deps.storage, Operation::Burns)?;
+        let owner_addr = deps.api.addr_validate(&owner)?;
+
+        // Spend the allowance, then burn the owner's tokens
+        deduct_allowance(deps.storage, &owner_addr, &info.sender, &env.block, amount)?;
+        debit(deps.storage, &owner_addr, amount, env.block.height)?;
+        reduce_total_supply(deps.storage, amount, env.block.height)?;
+
+        Ok(Response::new()
+            .add_event(events::BurnFrom {
+                owner: owner_addr,
+                spender: info.sender,
+                amount,
+            }))
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(mut deps: DepsMut, env: Env, msg: MigrateMsg) -> Result<Response, ContractError> {
+    // Only versions of this token can be migrated, and never to an older version
+    let stored = get_contract_version(deps.storage)?;
+    let stored_version: Version = stored.version.parse()?;
+    let current_version: Version = CONTRACT_VERSION.parse()?;
+    if stored_version > current_version {
+        return Err(ContractError::CannotDowngrade {
+            stored: stored.version,
+            current: CONTRACT_VERSION.to_string(),
+        });
+    }
+
+    match stored.contract.as_str() {
+        LEGACY_CONTRACT_NAME => migrate::from_legacy(deps.branch(), msg)?,
+        // 0.1.0 has no configuration and stores vesting and pool releases in a different layout
+        CONTRACT_NAME if stored_version < Version::new(0, 2, 0) => migrate::from_v0_1(deps.branch(), msg)?,
+        CONTRACT_NAME => {}
+        _ => return Err(ContractError::WrongContract { name: stored.contract }),
+    }
+    migrate::seed_roles(deps.branch())?;
+    migrate::seed_total_supply(deps.branch(), env.block.height)?;
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    Ok(Response::new()
+        .add_event(events::Migrated {
+            from_contract: stored.contract,
+            from_version: stored.version,
+            to_version: CONTRACT_VERSION.to_string(),
+        }))
+}
+
+pub mod migrate {
+    use super::*;
+    use crate::vesting::{Clock, Curve};
+    use cw_storage_plus::Map;
+    use serde::{Deserialize, Serialize};
+
+    /// Vesting and pool release records as stored by 0.1.0, keyed by `(beneficiary, Option<u64>, Option<u32>)`.
+    const LEGACY_VESTING: Map<&[u8], LegacyReleaseInfo> = Map::new("vesting");
+    const LEGACY_POOL_RELEASE_SCHEDULE: Map<&[u8], LegacyReleaseInfo> = Map::new("pool_release_schedule");
+
+    /// A 0.1.0 vesting or pool release record. Releasing removed the matured steps and deducted them from
+    /// `amount`, so both only describe what is still locked. Pool records have no start time.
+    #[derive(Serialize, Deserialize)]
+    struct LegacyReleaseInfo {
+        amount: Uint128,
+        #[serde(default)]
+        start_time: Option<Timestamp>,
+        release_schedule: Vec<(Timestamp, Uint128)>,
+    }
+
+    /// Upgrades the legacy layout, which shares the token info and balances with the current one but has
+    /// no configuration or metadata. Nothing is vested, so no vesting records are created.
+    pub fn from_legacy(mut deps: DepsMut, msg: MigrateMsg) -> Result<(), ContractError> {
+        let pool = msg.pool_address.map(|pool| deps.api.addr_validate(&pool)).transpose()?;
+        init_config(deps.branch(), pool, msg.treasury)?;
+        save_metadata_url(deps, msg.metadata_url)
+    }
+
+    /// Upgrades 0.1.0 instances, which have no configuration and store vesting and pool release records
+    /// with incremental steps under a composite key. Each record is rewritten as a stepped schedule of
+    /// what is still locked, with nothing released yet.
+    pub fn from_v0_1(mut deps: DepsMut, msg: MigrateMsg) -> Result<(), ContractError> {
+        convert_records(deps.branch(), &LEGACY_VESTING, &VESTING)?;
+        let pool_beneficiaries = convert_records(deps.branch(), &LEGACY_POOL_RELEASE_SCHEDULE, &POOL_RELEASE_SCHEDULE)?;
+
+        // The pool allocation went to the pool release beneficiary, unless the migration names another one
+        let pool = match msg.pool_address {
+            Some(pool) => Some(deps.api.addr_validate(&pool)?),
+            None => match pool_beneficiaries.as_slice() {
+                [pool] => Some(pool.clone()),
+                _ => None,
+            },
+        };
+        init_config(deps.branch(), pool, msg.treasury)?;
+        save_metadata_url(deps, msg.metadata_url)
+    }
+
+    /// Moves every legacy record of `legacy` into `records` and returns the beneficiaries.
+    /// Fully released records are dropped.
+    fn convert_records(
+        deps: DepsMut,
+        legacy: &Map<&[u8], LegacyReleaseInfo>,
+        records: &Map<&Addr, VestingInfo>,
+    ) -> Result<Vec<Addr>, ContractError> {
+        let entries = legacy
+            .range_raw(deps.storage, None, None, Order::Ascending)
+            .collect::<StdResult<Vec<_>>>()?;
+
+        let mut beneficiaries = vec![];
+        for (key, legacy_info) in entries {
+            legacy.remove(deps.storage, &key);
+            let beneficiary = legacy_beneficiary(&key)?;
+            if legacy_info.amount.is_zero() {
+                continue;
+            }
+            if records.has(deps.storage, &beneficiary) {
+                return Err(StdError::generic_err(format!("Multiple legacy records for {beneficiary}")).into());
+            }
+            records.save(deps.storage, &beneficiary, &convert_release_info(legacy_info))?;
+            beneficiaries.push(beneficiary);
+        }
+        Ok(beneficiaries)
+    }
+
+    /// Returns the beneficiary of a legacy record key. Composite keys length-prefix the address, while
+    /// records saved under the address alone use it as the whole key.
+    fn legacy_beneficiary(key: &[u8]) -> StdResult<Addr> {
+        let prefixed_len = key.get(..2).map(|len| usize::from(u16::from_be_bytes([len[0], len[1]])));
+        let address = match prefixed_len {
+            Some(len) if 2 + len <= key.len() => &key[2..2 + len],
+            _ => key,
+        };
+        let address = String::from_utf8(address.to_vec())
+            .map_err(|_| StdError::generic_err("Invalid address in legacy record key"))?;
+        Ok(Addr::unchecked(address))
+    }
+
+    /// Turns incremental release steps into a cumulative stepped schedule over the still locked amount.
+    /// Whatever the steps leave locked unlocks with the last one.
+    fn convert_release_info(legacy: LegacyReleaseInfo) -> VestingInfo {
+        let mut release_schedule = legacy.release_schedule;
+        release_schedule.sort_by_key(|(time, _)| *time);
+        let start_time = legacy
+            .start_time
+            .or_else(|| release_schedule.first().map(|(time, _)| *time))
+            .unwrap_or_default();
+
+        let mut steps: Vec<(u64, Uint128)> = vec![];
+        let mut cumulative = Uint128::zero();
+        for (time, amount) in release_schedule {
+            cumulative += amount;
+            match steps.last_mut() {
+                Some(last) if last.0 == time.seconds() => last.1 = cumulative,
+                _ => steps.push((time.seconds(), cumulative)),
+            }
+        }
+
+        match steps.last_mut() {
+            Some(last) => last.1 = last.1.max(legacy.amount),
+            None => steps.push((start_time.seconds(), legacy.amount)),
+        }
+
+        VestingInfo {
+            amount: legacy.amount,
+            released: Uint128::zero(),
+            start_time,
+            schedule: Schedule { clock: Clock::Time, curve: Curve::Stepped { steps } },
+            revoker: None,
+        }
+    }
+
+    /// Creates the configuration of instances that predate it. The treasury defaults to the owner.
+    fn init_config(deps: DepsMut, pool: Option<Addr>, treasury: Option<String>) -> Result<(), ContractError> {
+        if CONFIG.exists(deps.storage) {
+            return Ok(());
+        }
+        let treasury = treasury
+            .map(|treasury| deps.api.addr_validate(&treasury))
+            .transpose()?
+            .or(TOKEN_INFO.load(deps.storage)?.owner)
+            .ok_or_else(|| StdError::generic_err("A treasury is required when the token has no owner"))?;
+        let config = Config {
+            pool,
+            treasury,
+            keeper_tip: Uint128::zero(),
+            keeper_min_release: Uint128::zero(),
+            vesting_votes: false,
+            unbonding_period: 0,
+            max_batch_size: DEFAULT_MAX_BATCH_SIZE,
+        };
+        CONFIG.save(deps.storage, &config)?;
+        Ok(())
+    }
+
+    fn save_metadata_url(deps: DepsMut, metadata_url: Option<String>) -> Result<(), ContractError> {
+        if let Some(metadata_url) = metadata_url {
+            if !is_valid_url(&metadata_url) {
+                return Err(ContractError::InvalidMetadata { url: metadata_url });
+            }
+            METADATA_URL.save(deps.storage, &metadata_url)?;
+        }
+        Ok(())
+    }
+
+    /// Starts the total supply history of instances that predate it at the current supply.
+    /// Balances keep their storage namespace, so they need no conversion.
+    pub fn seed_total_supply(deps: DepsMut, height: u64) -> Result<(), ContractError> {
+        if TOTAL_SUPPLY.may_load(deps.storage)?.is_none() {
+            let total_supply = TOKEN_INFO.load(deps.storage)?.total_supply;
+            TOTAL_SUPPLY.save(deps.storage, &total_supply, height)?;
+        }
+        Ok(())
+    }
+
+    /// Grants every role to the owner of instances that predate roles, so privileged actions keep working.
+    pub fn seed_roles(deps: DepsMut) -> Result<(), ContractError> {
+        if !ROLES.is_empty(deps.storage) {
+            return Ok(());
+        }
+        if let Some(owner) = TOKEN_INFO.load(deps.storage)?.owner {
+            for role in Role::ALL {
+                ROLES.save(deps.storage, (role.as_str(), &owner), &Empty {})?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Balance { address } => to_json_binary(&query::balance(deps, address)?),
+        QueryMsg::BalanceAtHeight { address, height } => {
+            to_json_binary(&query::balance_at_height(deps, address, height)?)
+        }
+        QueryMsg::TokenInfo {} => to_json_binary(&query::token_info(deps)?),
+        QueryMsg::TotalSupplyAtHeight { height } => to_json_binary(&query::total_supply_at_height(deps, height)?),
+        QueryMsg::Minter {} => to_json_binary(&query::minter(deps, env)?),
+        QueryMsg::MarketingInfo {} => to_json_binary(&query::marketing_info(deps)?),
+        QueryMsg::DownloadLogo {} => to_json_binary(&query::download_logo(deps)?),
+        QueryMsg::AllAccounts { start_after, limit } => {
+            to_json_binary(&query::all_accounts(deps, start_after, limit)?)
+        }
+        QueryMsg::PendingOwner {} => to_json_binary(&query::pending_owner(deps)?),
+        QueryMsg::PauseStatus {} => to_json_binary(&query::pause_status(deps)?),
+        QueryMsg::FrozenAccounts { start_after, limit } => {
+            to_json_binary(&query::frozen_accounts(deps, start_after, limit)?)
+        }
+        QueryMsg::VotingPower { address, height } => to_json_binary(&query::voting_power(deps, address, height)?),
+        QueryMsg::Delegates { address } => to_json_binary(&query::delegates(deps, address)?),
+        QueryMsg::Staked { address } => to_json_binary(&query::staked(deps, address)?),
+        QueryMsg::PendingRewards { address } => to_json_binary(&query::pending_rewards(deps, env, address)?),
+        QueryMsg::UnbondingEntries { address } => to_json_binary(&query::unbonding_entries(deps, address)?),
+        QueryMsg::MerkleStage { stage } => to_json_binary(&query::merkle_stage(deps, stage)?),
+        QueryMsg::IsClaimed { stage, address } => to_json_binary(&query::is_claimed(deps, stage, address)?),
+        QueryMsg::Nonce { address } => to_json_binary(&query::nonce(deps, address)?),
+        QueryMsg::NativeDenom {} => to_json_binary(&query::native_denom(deps)?),
+        QueryMsg::ListChannels { start_after, limit } => {
+            to_json_binary(&query::list_channels(deps, start_after, limit)?)
+        }
+        QueryMsg::FeeConfig {} => to_json_binary(&query::fee_config(deps)?),
+        QueryMsg::HasRole { role, address } => to_json_binary(&query::has_role(deps, role, address)?),
+        QueryMsg::ListRoleMembers { role, start_after, limit } => {
+            to_json_binary(&query::list_role_members(deps, role, start_after, limit)?)
+        }
+        // Deprecated aliases kept for existing tooling
+        QueryMsg::GetTokenInfo {} => to_json_binary(&query::get_token_info(deps)?),
+        QueryMsg::GetBalance { address } => to_json_binary(&query::balance(deps, address)?.balance),
+        QueryMsg::GetMetadata {} => to_json_binary(&query::metadata(deps)?),
+        QueryMsg::GetVestingInfo { address } => to_json_binary(&query::vesting_info(deps, address)?),
+        QueryMsg::GetPoolReleaseInfo { address } => to_json_binary(&query::pool_release_info(deps, address)?),
+        QueryMsg::Claimable { address } => to_json_binary(&query::claimable(deps, env, address)?),
+        QueryMsg::VestingProjection { address, at_time, at_height } => {
+            to_json_binary(&query::vesting_projection(deps, env, address, at_time, at_height)?)
+        }
+        QueryMsg::KeeperInfo {} => to_json_binary(&query::keeper_info(deps)?),
+        QueryMsg::ListVestingAccounts { start_after, limit } => {
+            to_json_binary(&query::list_vesting_accounts(deps, start_after, limit)?)
+        }
+        QueryMsg::Allowance { owner, spender } => to_json_binary(&query::allowance(deps, owner, spender)?),
+        QueryMsg::AllAllowances { owner, start_after, limit } => {
+            to_json_binary(&query::all_allowances(deps, owner, start_after, limit)?)
+        }
+        QueryMsg::AllSpenderAllowances { spender, start_after, limit } => {
+            to_json_binary(&query::all_spender_allowances(deps, spender, start_after, limit)?)
+        }
+    }
+}
+
+pub mod query {
+    use super::*;
+
+    // Pagination defaults for list queries
+    const DEFAULT_LIMIT: u32 = 10;
+    const MAX_LIMIT: u32 = 30;
+
+    pub fn balance(deps: Deps, address: String) -> StdResult<BalanceResponse> {
+        let addr = deps.api.addr_validate(&address)?;
+        let balance = BALANCES.may_load(deps.storage, &addr)?.unwrap_or_default();
+        Ok(BalanceResponse { balance })
+    }
+
+    pub fn balance_at_height(deps: Deps, address: String, height: Option<u64>) -> StdResult<BalanceResponse> {
+        let addr = deps.api.addr_validate(&address)?;
+        let balance = match height {
+            Some(height) => BALANCES.may_load_at_height(deps.storage, &addr, height)?,
+            None => BALANCES.may_load(deps.storage, &addr)?,
+        };
+        Ok(BalanceResponse {
+            balance: balance.unwrap_or_default(),
+        })
+    }
+
+    pub fn total_supply_at_height(deps: Deps, height: Option<u64>) -> StdResult<TotalSupplyResponse> {
+        let total_supply = match height {
+            Some(height) => TOTAL_SUPPLY.may_load_at_height(deps.storage, height)?,
+            None => TOTAL_SUPPLY.may_load(deps.storage)?,
+        };
+        Ok(TotalSupplyResponse {
+            total_supply: total_supply.unwrap_or_default(),
+        })
+    }
+
+    pub fn token_info(deps: Deps) -> StdResult<TokenInfoResponse> {
+        let token_info = TOKEN_INFO.load(deps.storage)?;
+        Ok(TokenInfoResponse {
+            name: token_info.name,
+            symbol: token_info.symbol,
+            decimals: token_info.decimals,
+            total_supply: token_info.total_supply,
+        })
+    }
+
+    pub fn minter(deps: Deps, env: Env) -> StdResult<Option<MinterResponse>> {
+        let token_info = TOKEN_INFO.load(deps.storage)?;
+        let mintable = mintable(&token_info, &env.block);
+        Ok(token_info.mint.map(|mint| MinterResponse {
+            minter: mint.minter.into_string(),
+            cap: mint.cap,
+            mintable,
+        }))
+    }
+
+    pub fn marketing_info(deps: Deps) -> StdResult<MarketingInfoResponse> {
+        let metadata_url = METADATA_URL.may_load(deps.storage)?;
+        Ok(MarketingInfoResponse {
+            logo: metadata_url.map(LogoInfo::Url),
+            ..MarketingInfoResponse::default()
+        })
+    }
+
+    pub fn download_logo(_deps: Deps) -> StdResult<DownloadLogoResponse> {
+        // Only a logo URL is stored, there is no embedded logo to download
+        Err(StdError::not_found("logo"))
+    }
+
+    pub fn all_accounts(
+        deps: Deps,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<AllAccountsResponse> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let start_addr = start_after.map(|s| deps.api.addr_validate(&s)).transpose()?;
+        let start = start_addr.as_ref().map(Bound::exclusive);
+
+        let accounts = BALANCES
+            .keys(deps.storage, start, None, Order::Ascending)
+            .take(limit)
+            .map(|item| item.map(Addr::into_string))
+            .collect::<StdResult<_>>()?;
+        Ok(AllAccountsResponse { accounts })
+    }
+
+    pub fn get_token_info(deps: Deps) -> StdResult<GetTokenInfoResponse> {
+        let token_info = TOKEN_INFO.load(deps.storage)?;
+        Ok(GetTokenInfoResponse {
+            name: token_info.name,
+            symbol: token_info.symbol,
+            decimals: token_info.decimals,
+            total_supply: token_info.total_supply,
+            owner: token_info.owner.map(Addr::into_string),
+        })
+    }
+
+    pub fn pending_owner(deps: Deps) -> StdResult<PendingOwnerResponse> {
+        let token_info = TOKEN_INFO.load(deps.storage)?;
+        let pending_owner = PENDING_OWNER.may_load(deps.storage)?;
+        Ok(PendingOwnerResponse {
+            owner: token_info.owner.map(Addr::into_string),
+            expiry: pending_owner.as_ref().and_then(|pending| pending.expiry),
+            pending_owner: pending_owner.map(|pending| pending.new_owner.into_string()),
+        })
+    }
+
+    pub fn pause_status(deps: Deps) -> StdResult<PauseStatusResponse> {
+        let pause_state = PAUSE_STATE.may_load(deps.storage)?.unwrap_or_default();
+        Ok(PauseStatusResponse {
+            transfers: pause_state.transfers,
+            burns: pause_state.burns,
+            vesting_releases: pause_state.vesting_releases,
+            pool_releases: pause_state.pool_releases,
+            minting: pause_state.minting,
+        })
+    }
+
+    pub fn frozen_accounts(
+        deps: Deps,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<FrozenAccountsResponse> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let start_addr = start_after.map(|s| deps.api.addr_validate(&s)).transpose()?;
+        let start = start_addr.as_ref().map(Bound::exclusive);
+
+        let accounts = FROZEN
+            .range(deps.storage, start, None, Order::Ascending)
+            .take(limit)
+            .map(|item| {
+                item.map(|(address, reason)| FrozenAccount {
+                    address: address.into_string(),
+                    reason,
+                })
+            })
+            .collect::<StdResult<_>>()?;
+        Ok(FrozenAccountsResponse { accounts })
+    }
+
+    pub fn voting_power(deps: Deps, address: String, height: Option<u64>) -> StdResult<VotingPowerResponse> {
+        let addr = deps.api.addr_validate(&address)?;
+        let power = match height {
+            Some(height) => VOTES.may_load_at_height(deps.storage, &addr, height)?,
+            None => VOTES.may_load(deps.storage, &addr)?,
+        };
+        Ok(VotingPowerResponse {
+            power: power.unwrap_or_default(),
+        })
+    }
+
+    pub fn delegates(deps: Deps, address: String) -> StdResult<DelegatesResponse> {
+        let addr = deps.api.addr_validate(&address)?;
+        let delegate = DELEGATES.may_load(deps.storage, &addr)?;
+        Ok(DelegatesResponse {
+            delegate: delegate.map(Addr::into_string),
+        })
+    }
+
+    pub fn staked(deps: Deps, address: String) -> StdResult<StakedResponse> {
+        let addr = deps.api.addr_validate(&address)?;
+        let stake = STAKES.may_load(deps.storage, &addr)?.unwrap_or_default();
+        let staking = STAKING.may_load(deps.storage)?.unwrap_or_default();
+        Ok(StakedResponse {
+            staked: stake.amount,
+            total_staked: staking.total_staked,
+        })
+    }
+
+    pub fn pending_rewards(deps: Deps, env: Env, address: String) -> StdResult<PendingRewardsResponse> {
+        let addr = deps.api.addr_validate(&address)?;
+        let stake = STAKES.may_load(deps.storage, &addr)?.unwrap_or_default();
+        let staking = STAKING.may_load(deps.storage)?.unwrap_or_default();
+        let rewards = stake.earned(staking.reward_per_token_at(env.block.time.seconds()))?;
+        Ok(PendingRewardsResponse { rewards })
+    }
+
+    pub fn unbonding_entries(deps: Deps, address: String) -> StdResult<UnbondingEntriesResponse> {
+        let addr = deps.api.addr_validate(&address)?;
+        let entries = UNBONDING.may_load(deps.storage, &addr)?.unwrap_or_default();
+        Ok(UnbondingEntriesResponse { entries })
+    }
+
+    pub fn merkle_stage(deps: Deps, stage: u8) -> StdResult<MerkleStageResponse> {
+        let merkle_stage = MERKLE_STAGES.load(deps.storage, stage)?;
+        Ok(MerkleStageResponse {
+            stage,
+            root: merkle_stage.root,
+            expiration: merkle_stage.expiration,
+            total_amount: merkle_stage.total_amount,
+            claimed_amount: merkle_stage.claimed_amount,
+            reclaimed: merkle_stage.reclaimed,
+        })
+    }
+
+    pub fn is_claimed(deps: Deps, stage: u8, address: String) -> StdResult<IsClaimedResponse> {
+        let addr = deps.api.addr_validate(&address)?;
+        Ok(IsClaimedResponse {
+            is_claimed: AIRDROP_CLAIMS.has(deps.storage, (stage, &addr)),
+        })
+    }
+
+    pub fn nonce(deps: Deps, address: String) -> StdResult<NonceResponse> {
+        let addr = deps.api.addr_validate(&address)?;
+        let nonce = NONCES.may_load(deps.storage, &addr)?.unwrap_or_default();
+        Ok(NonceResponse { nonce })
+    }
+
+    pub fn native_denom(deps: Deps) -> StdResult<NativeDenomResponse> {
+        Ok(NativeDenomResponse {
+            denom: NATIVE_DENOM.may_load(deps.storage)?,
+            escrowed: NATIVE_ESCROW.may_load(deps.storage)?.unwrap_or_default(),
+        })
+    }
+
+    pub fn list_channels(
+        deps: Deps,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<ListChannelsResponse> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let start = start_after.as_deref().map(Bound::exclusive);
+
+        let channels = CHANNELS
+            .range(deps.storage, start, None, Order::Ascending)
+            .take(limit)
+            .map(|item| {
+                let (channel_id, info) = item?;
+                Ok(ChannelResponse {
+                    allowed: CHANNEL_ALLOWLIST.has(deps.storage, &channel_id),
+                    escrowed: CHANNEL_ESCROW.may_load(deps.storage, &channel_id)?.unwrap_or_default(),
+                    channel_id,
+                    counterparty_endpoint: info.counterparty_endpoint,
+                    connection_id: info.connection_id,
+                })
+            })
+            .collect::<StdResult<_>>()?;
+        Ok(ListChannelsResponse { channels })
+    }
+
+    pub fn fee_config(deps: Deps) -> StdResult<FeeConfigResponse> {
+        let fee_config = FEE_CONFIG.may_load(deps.storage)?.unwrap_or_default();
+        Ok(FeeConfigResponse {
+            rate_bps: fee_config.rate_bps,
+            treasury_share_bps: fee_config.treasury_share_bps,
+            burn_share_bps: fee_config.burn_share_bps,
+            pool_share_bps: fee_config.pool_share_bps,
+        })
+    }
+
+    pub fn has_role(deps: Deps, role: Role, address: String) -> StdResult<HasRoleResponse> {
+        let addr = deps.api.addr_validate(&address)?;
+        let has_role = ROLES.has(deps.storage, (role.as_str(), &addr));
+        Ok(HasRoleResponse { has_role })
+    }
+
+    pub fn list_role_members(
+        deps: Deps,
+        role: Role,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<RoleMembersResponse> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let start_addr = start_after.map(|s| deps.api.addr_validate(&s)).transpose()?;
+        let start = start_addr.as_ref().map(Bound::exclusive);
+
+        let members = ROLES
+            .prefix(role.as_str())
+            .keys(deps.storage, start, None, Order::Ascending)
+            .take(limit)
+            .map(|item| item.map(Addr::into_string))
+            .collect::<StdResult<_>>()?;
+        Ok(RoleMembersResponse { members })
+    }
+
+    pub fn vesting_info(deps: Deps, address: String) -> StdResult<VestingInfoResponse> {
+        let addr = deps.api.addr_validate(&address)?;
+        let vesting_info = VESTING.load(deps.storage, &addr)?;
+        Ok(VestingInfoResponse {
+            amount: vesting_info.amount,
+            released: vesting_info.released,
+            start_time: vesting_info.start_time.seconds(),
+            schedule: vesting_info.schedule,
+            revoker: vesting_info.revoker.map(Addr::into_string),
+        })
+    }
+
+    pub fn pool_release_info(deps: Deps, address: String) -> StdResult<PoolReleaseInfoResponse> {
+        let addr = deps.api.addr_validate(&address)?;
+        let pool_release_info = POOL_RELEASE_SCHEDULE.load(deps.storage, &addr)?;
+        Ok(PoolReleaseInfoResponse {
+            amount: pool_release_info.amount,
+            released: pool_release_info.released,
+            schedule: pool_release_info.schedule,
+        })
+    }
+
+    pub fn claimable(deps: Deps, env: Env, address: String) -> StdResult<ClaimableResponse> {
+        let addr = deps.api.addr_validate(&address)?;
+        Ok(ClaimableResponse {
+            vesting: VESTING.may_load(deps.storage, &addr)?.map(|v| v.status(&env.block)),
+            pool: POOL_RELEASE_SCHEDULE.may_load(deps.storage, &addr)?.map(|p| p.status(&env.block)),
+        })
+    }
+
+    pub fn vesting_projection(
+        deps: Deps,
+        env: Env,
+        address: String,
+        at_time: u64,
+        at_height: Option<u64>,
+    ) -> StdResult<VestingProjectionResponse> {
+        let addr = deps.api.addr_validate(&address)?;
+        let block = BlockInfo {
+            height: at_height.unwrap_or(env.block.height),
+            time: Timestamp::from_seconds(at_time),
+            chain_id: env.block.chain_id,
+        };
+
+        let vesting = VESTING.may_load(deps.storage, &addr)?.map(|v| v.status(&block));
+        let pool = POOL_RELEASE_SCHEDULE.may_load(deps.storage, &addr)?.map(|p| p.status(&block));
+        let balance = BALANCES.may_load(deps.storage, &addr)?.unwrap_or_default();
+        let projected_balance = [&vesting, &pool]
+            .into_iter()
+            .flatten()
+            .try_fold(balance, |total, status| total.checked_add(status.claimable_now))?;
+
+        Ok(VestingProjectionResponse {
+            at_time,
+            at_height: block.height,
+            vesting,
+            pool,
+            projected_balance,
+        })
+    }
+
+    pub fn keeper_info(deps: Deps) -> StdResult<KeeperInfoResponse> {
+        let config = CONFIG.load(deps.storage)?;
+        let reserve = KEEPER_RESERVE.may_load(deps.storage)?.unwrap_or_default();
+        Ok(KeeperInfoResponse {
+            tip: config.keeper_tip,
+            min_release: config.keeper_min_release,
+            reserve,
+        })
+    }
+
+    pub fn list_vesting_accounts(
+        deps: Deps,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<VestingAccountsResponse> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let start_addr = start_after.map(|s| deps.api.addr_validate(&s)).transpose()?;
+        let start = start_addr.as_ref().map(Bound::exclusive);
+
+        let accounts = VESTING
+            .range(deps.storage, start, None, Order::Ascending)
+            .take(limit)
+            .map(|item| {
+                item.map(|(address, vesting_info)| VestingAccount {
+                    address: address.into_string(),
+                    amount: vesting_info.amount,
+                    released: vesting_info.released,
+                    start_time: vesting_info.start_time.seconds(),
+                    schedule: vesting_info.schedule,
+                    revoker: vesting_info.revoker.map(Addr::into_string),
+                })
+            })
+            .collect::<StdResult<_>>()?;
+        Ok(VestingAccountsResponse { accounts })
     }
 
     pub fn metadata(deps: Deps) -> StdResult<MetadataResponse> {
         let metadata_url = METADATA_URL.load(deps.storage)?;
         Ok(MetadataResponse { metadata_url })
     }
-}
-fn is_valid_url(url: &str) -> bool {
-    url::Url::parse(url).is_ok()
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use cosmwasm_std::testing::{mock_dependencies, mock_env};
-    use cosmwasm_std::{coins, from_binary, Addr, MessageInfo};
+    pub fn allowance(deps: Deps, owner: String, spender: String) -> StdResult<AllowanceResponse> {
+        let owner_addr = deps.api.addr_validate(&owner)?;
+        let spender_addr = deps.api.addr_validate(&spender)?;
+        let allowance = ALLOWANCES
+            .may_load(deps.storage, (&owner_addr, &spender_addr))?
+            .unwrap_or_default();
+        Ok(AllowanceResponse {
+            allowance: allowance.allowance,
+            expires: allowance.expires,
+        })
+    }
+
+    pub fn all_allowances(
+        deps: Deps,
+        owner: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<AllAllowancesResponse> {
+        let owner_addr = deps.api.addr_validate(&owner)?;
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let start_addr = start_after.map(|s| deps.api.addr_validate(&s)).transpose()?;
+        let start = start_addr.as_ref().map(Bound::exclusive);
+
+        let allowances = ALLOWANCES
+            .prefix(&owner_addr)
+            .range(deps.storage, start, None, Order::Ascending)
+            .take(limit)
+            .map(|item| {
+                item.map(|(spender, allowance)| AllowanceInfo {
+                    spender: spender.into_string(),
+                    allowance: allowance.allowance,
+                    expires: allowance.expires,
+                })
+            })
+            .collect::<StdResult<_>>()?;
+        Ok(AllAllowancesResponse { allowances })
+    }
+
+    pub fn all_spender_allowances(
+        deps: Deps,
+        spender: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<AllSpenderAllowancesResponse> {
+        let spender_addr = deps.api.addr_validate(&spender)?;
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let start_addr = start_after.map(|s| deps.api.addr_validate(&s)).transpose()?;
+        let start = start_addr.as_ref().map(Bound::exclusive);
+
+        let allowances = ALLOWANCES_SPENDER
+            .prefix(&spender_addr)
+            .range(deps.storage, start, None, Order::Ascending)
+            .take(limit)
+            .map(|item| {
+                item.map(|(owner, allowance)| SpenderAllowanceInfo {
+                    owner: owner.into_string(),
+                    allowance: allowance.allowance,
+                    expires: allowance.expires,
+                })
+            })
+            .collect::<StdResult<_>>()?;
+        Ok(AllSpenderAllowancesResponse { allowances })
+    }
+}
+fn is_valid_url(url: &str) -> bool {
+    url::Url::parse(url).is_ok()
+}
+
+/// Fails unless `sender` is the current owner. Always fails once ownership has been renounced.
+fn ensure_owner(storage: &dyn Storage, sender: &Addr) -> Result<(), ContractError> {
+    let token_info = TOKEN_INFO.load(storage)?;
+    if token_info.owner.as_ref() != Some(sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+    Ok(())
+}
+
+/// Returns the pool address if `sender` may spend the pool's balance: the pool itself, or the owner on its
+/// behalf, since the pool is often a contract that cannot call this one.
+fn ensure_pool_operator(storage: &dyn Storage, sender: &Addr) -> Result<Addr, ContractError> {
+    let pool = CONFIG.load(storage)?.pool.ok_or(ContractError::Unauthorized {})?;
+    if *sender != pool {
+        ensure_owner(storage, sender)?;
+    }
+    Ok(pool)
+}
+
+/// Returns how much can be minted at `block` under the cap and the minting schedule, or `None` when
+/// neither limits it. Returns zero when minting is disabled.
+fn mintable(token_info: &TokenInfo, block: &BlockInfo) -> Option<Uint128> {
+    let Some(mint) = &token_info.mint else {
+        return Some(Uint128::zero());
+    };
+    let under_cap = mint.cap.map(|cap| cap.saturating_sub(token_info.total_supply));
+    let under_schedule = mint
+        .schedule
+        .as_ref()
+        .map(|schedule| schedule.vested_at(block).saturating_sub(mint.minted));
+    match (under_cap, under_schedule) {
+        (Some(cap), Some(schedule)) => Some(cap.min(schedule)),
+        (cap, schedule) => cap.or(schedule),
+    }
+}
+
+/// Fails with `Paused` if a pauser has paused `operation`.
+pub(crate) fn ensure_not_paused(storage: &dyn Storage, operation: Operation) -> Result<(), ContractError> {
+    let pause_state = PAUSE_STATE.may_load(storage)?.unwrap_or_default();
+    if pause_state.is_paused(operation) {
+        return Err(ContractError::Paused { operation: operation.as_str().to_string() });
+    }
+    Ok(())
+}
+
+/// Fails unless `sender` has been granted `role`.
+fn ensure_role(storage: &dyn Storage, role: Role, sender: &Addr) -> Result<(), ContractError> {
+    if !ROLES.has(storage, (role.as_str(), sender)) {
+        return Err(ContractError::MissingRole { role: role.as_str().to_string() });
+    }
+    Ok(())
+}
+
+/// Fails with `AccountFrozen` if `addr` has been frozen.
+fn ensure_not_frozen(storage: &dyn Storage, addr: &Addr) -> Result<(), ContractError> {
+    if FROZEN.has(storage, addr) {
+        return Err(ContractError::AccountFrozen { address: addr.to_string() });
+    }
+    Ok(())
+}
+
+/// Deducts `amount` from the balance of `addr`, failing if the balance is too low or the account is frozen.
+fn debit(storage: &mut dyn Storage, addr: &Addr, amount: Uint128, height: u64) -> Result<Uint128, ContractError> {
+    ensure_not_frozen(storage, addr)?;
+    let balance = BALANCES.update(storage, addr, height, |balance| -> Result<_, ContractError> {
+        let available = balance.unwrap_or_default();
+        available
+            .checked_sub(amount)
+            .map_err(|_| ContractError::InsufficientBalance { required: amount, available })
+    })?;
+    remove_weight(storage, addr, amount, height)?;
+    Ok(balance)
+}
+
+/// Adds `amount` to the balance of `addr`, failing if the account is frozen.
+pub(crate) fn credit(storage: &mut dyn Storage, addr: &Addr, amount: Uint128, height: u64) -> Result<Uint128, ContractError> {
+    ensure_not_frozen(storage, addr)?;
+    credit_refund(storage, addr, amount, height)
+}
+
+/// Adds `amount` to the balance of `addr` even if the account is frozen. Only for tokens the contract owes
+/// `addr` regardless of the freeze, such as refunds, a revoked vesting or transfer fee shares, which would
+/// otherwise stay stuck or block other accounts; the freeze still keeps them from moving on.
+pub(crate) fn credit_refund(
+    storage: &mut dyn Storage,
+    addr: &Addr,
+    amount: Uint128,
+    height: u64,
+) -> Result<Uint128, ContractError> {
+    let balance = BALANCES.update(storage, addr, height, |balance| -> Result<_, ContractError> {
+        balance
+            .unwrap_or_default()
+            .checked_add(amount)
+            .map_err(|_| ContractError::Overflow {})
+    })?;
+    add_weight(storage, addr, amount, height)?;
+    Ok(balance)
+}
+
+/// Accrues staking rewards up to `now`, then brings `staker`'s earned rewards up to date.
+/// The caller saves both once it has changed the stake.
+fn update_rewards(storage: &dyn Storage, staker: &Addr, now: u64) -> StdResult<(StakingState, Stake)> {
+    let mut staking = STAKING.may_load(storage)?.unwrap_or_default();
+    staking.accrue(now);
+    let mut stake = STAKES.may_load(storage, staker)?.unwrap_or_default();
+    stake.settle(staking.reward_per_token)?;
+    Ok((staking, stake))
+}
+
+/// Returns the voting weight `holder` can delegate: its balance, plus its locked vesting if that counts.
+fn voting_weight(storage: &dyn Storage, holder: &Addr) -> StdResult<Uint128> {
+    let balance = BALANCES.may_load(storage, holder)?.unwrap_or_default();
+    if !CONFIG.load(storage)?.vesting_votes {
+        return Ok(balance);
+    }
+    let locked = VESTING
+        .may_load(storage, holder)?
+        .map(|vesting_info| vesting_info.amount - vesting_info.released)
+        .unwrap_or_default();
+    Ok(balance.checked_add(locked)?)
+}
+
+/// Adds `amount` to the votes of whoever `holder` has delegated to.
+fn add_weight(storage: &mut dyn Storage, holder: &Addr, amount: Uint128, height: u64) -> StdResult<()> {
+    if let Some(delegatee) = DELEGATES.may_load(storage, holder)? {
+        VOTES.update(storage, &delegatee, height, |votes| -> StdResult<_> {
+            Ok(votes.unwrap_or_default().checked_add(amount)?)
+        })?;
+    }
+    Ok(())
+}
+
+/// Removes `amount` from the votes of whoever `holder` has delegated to.
+fn remove_weight(storage: &mut dyn Storage, holder: &Addr, amount: Uint128, height: u64) -> StdResult<()> {
+    if let Some(delegatee) = DELEGATES.may_load(storage, holder)? {
+        VOTES.update(storage, &delegatee, height, |votes| -> StdResult<_> {
+            Ok(votes.unwrap_or_default().checked_sub(amount)?)
+        })?;
+    }
+    Ok(())
+}
+
+/// Stops counting `amount` of locked vesting toward `beneficiary`'s weight once it is released or revoked.
+/// Released tokens are counted again through the balance they are credited to.
+fn unlock_vesting_weight(storage: &mut dyn Storage, beneficiary: &Addr, amount: Uint128, height: u64) -> StdResult<()> {
+    if CONFIG.load(storage)?.vesting_votes {
+        remove_weight(storage, beneficiary, amount, height)?;
+    }
+    Ok(())
+}
+
+/// How the fee charged on a transfer was split.
+#[derive(Default)]
+struct TransferFee {
+    total: Uint128,
+    treasury: Uint128,
+    burned: Uint128,
+    pool: Uint128,
+}
+
+impl TransferFee {
+    /// Adds the fee charged on another transfer of the same batch.
+    fn accumulate(&mut self, other: &TransferFee) {
+        self.total += other.total;
+        self.treasury += other.treasury;
+        self.burned += other.burned;
+        self.pool += other.pool;
+    }
+}
+
+/// Moves `amount` from `from` to `to`, charging the transfer fee unless either side is exempt.
+/// The recipient receives `amount` minus the fee; any rounding remainder of the split goes to the treasury.
+fn transfer_with_fee(
+    storage: &mut dyn Storage,
+    from: &Addr,
+    to: &Addr,
+    amount: Uint128,
+    height: u64,
+) -> Result<TransferFee, ContractError> {
+    debit(storage, from, amount, height)?;
+    credit_with_fee(storage, from, to, amount, height)
+}
+
+/// Pays `to` the `amount` already debited from `from`, less the transfer fee, and distributes the fee.
+fn credit_with_fee(
+    storage: &mut dyn Storage,
+    from: &Addr,
+    to: &Addr,
+    amount: Uint128,
+    height: u64,
+) -> Result<TransferFee, ContractError> {
+    let fee_config = FEE_CONFIG.may_load(storage)?.unwrap_or_default();
+    let exempt = FEE_EXEMPT.has(storage, from) || FEE_EXEMPT.has(storage, to);
+    if fee_config.rate_bps == 0 || exempt {
+        credit(storage, to, amount, height)?;
+        return Ok(TransferFee::default());
+    }
+
+    let total = amount.multiply_ratio(fee_config.rate_bps, 10_000u128);
+    let burned = total.multiply_ratio(fee_config.burn_share_bps, 10_000u128);
+    let pool = total.multiply_ratio(fee_config.pool_share_bps, 10_000u128);
+    let treasury = total - burned - pool;
+    credit(storage, to, amount - total, height)?;
+
+    // A frozen fee destination must not block everyone else's transfers
+    let config = CONFIG.load(storage)?;
+    if !treasury.is_zero() {
+        credit_refund(storage, &config.treasury, treasury, height)?;
+    }
+    if !pool.is_zero() {
+        let pool_addr = config.pool.ok_or(ContractError::InvalidFeeConfig {})?;
+        credit_refund(storage, &pool_addr, pool, height)?;
+    }
+    if !burned.is_zero() {
+        reduce_total_supply(storage, burned, height)?;
+    }
+    Ok(TransferFee {
+        total,
+        treasury,
+        burned,
+        pool,
+    })
+}
+
+/// Removes `amount` from the recorded total supply after tokens are burned.
+fn reduce_total_supply(storage: &mut dyn Storage, amount: Uint128, height: u64) -> Result<TokenInfo, ContractError> {
+    let token_info = TOKEN_INFO.update(storage, |mut token_info| -> Result<_, ContractError> {
+        token_info.total_supply = token_info
+            .total_supply
+            .checked_sub(amount)
+            .map_err(|_| ContractError::Overflow {})?;
+        Ok(token_info)
+    })?;
+    TOTAL_SUPPLY.save(storage, &token_info.total_supply, height)?;
+    Ok(token_info)
+}
+
+/// Releases both the vesting and the pool release record of `beneficiary`, crediting it the total.
+/// Fails if the beneficiary has neither record.
+fn release_on_behalf(storage: &mut dyn Storage, beneficiary: &Addr, block: &BlockInfo) -> Result<Uint128, ContractError> {
+    let mut released = Uint128::zero();
+    let mut unlocked_vesting = Uint128::zero();
+    let mut found = false;
+    for (records, operation) in [
+        (&VESTING, Operation::VestingReleases),
+        (&POOL_RELEASE_SCHEDULE, Operation::PoolReleases),
+    ] {
+        if records.has(storage, beneficiary) {
+            ensure_not_paused(storage, operation)?;
+            found = true;
+            let amount = vesting::release(storage, records, beneficiary, block)?;
+            if operation == Operation::VestingReleases {
+                unlocked_vesting = amount;
+            }
+            released += amount;
+        }
+    }
+    if !found {
+        return Err(ContractError::NoVesting { address: beneficiary.to_string() });
+    }
+    credit(storage, beneficiary, released, block.height)?;
+    unlock_vesting_weight(storage, beneficiary, unlocked_vesting, block.height)?;
+    Ok(released)
+}
+
+/// Returns why `beneficiary` cannot be released on its behalf right now, if it cannot: it has no record, it is
+/// frozen, or releases of one of its records are paused. Checked before `release_on_behalf` touches storage.
+fn release_blocker(storage: &dyn Storage, beneficiary: &Addr) -> StdResult<Option<&'static str>> {
+    let has_vesting = VESTING.has(storage, beneficiary);
+    let has_pool_release = POOL_RELEASE_SCHEDULE.has(storage, beneficiary);
+    let pause_state = PAUSE_STATE.may_load(storage)?.unwrap_or_default();
+    let reason = if !has_vesting && !has_pool_release {
+        Some("no_record")
+    } else if FROZEN.has(storage, beneficiary) {
+        Some("frozen")
+    } else if (has_vesting && pause_state.is_paused(Operation::VestingReleases))
+        || (has_pool_release && pause_state.is_paused(Operation::PoolReleases))
+    {
+        Some("paused")
+    } else {
+        None
+    };
+    Ok(reason)
+}
+
+/// Whether releasing `released` earns a keeper tip. Releases below the configured minimum do not, so keepers
+/// cannot drain the reserve by pushing dust.
+fn earns_keeper_tip(storage: &dyn Storage, released: Uint128) -> StdResult<bool> {
+    let config = CONFIG.load(storage)?;
+    Ok(!released.is_zero() && released >= config.keeper_min_release)
+}
+
+/// Pays `keeper` the configured tip for `count` releases, capped by what is left in the reserve.
+fn pay_keeper_tip(
+    storage: &mut dyn Storage,
+    keeper: &Addr,
+    count: u128,
+    height: u64,
+) -> Result<Uint128, ContractError> {
+    let reserve = KEEPER_RESERVE.may_load(storage)?.unwrap_or_default();
+    let tip = CONFIG
+        .load(storage)?
+        .keeper_tip
+        .checked_mul(Uint128::new(count))
+        .map_err(|_| ContractError::Overflow {})?
+        .min(reserve);
+    if !tip.is_zero() {
+        KEEPER_RESERVE.save(storage, &(reserve - tip))?;
+        credit(storage, keeper, tip, height)?;
+    }
+    Ok(tip)
+}
+
+/// Spends `amount` of the allowance `owner` granted to `spender`, rejecting expired allowances and frozen
+/// spenders.
+fn deduct_allowance(
+    storage: &mut dyn Storage,
+    owner: &Addr,
+    spender: &Addr,
+    block: &BlockInfo,
+    amount: Uint128,
+) -> Result<Allowance, ContractError> {
+    ensure_not_frozen(storage, spender)?;
+    let mut allowance = ALLOWANCES
+        .may_load(storage, (owner, spender))?
+        .ok_or(ContractError::NoAllowance {})?;
+    if allowance.expires.is_expired(block) {
+        return Err(ContractError::Expired {});
+    }
+
+    let available = allowance.allowance;
+    allowance.allowance = available
+        .checked_sub(amount)
+        .map_err(|_| ContractError::InsufficientAllowance { required: amount, available })?;
+    ALLOWANCES.save(storage, (owner, spender), &allowance)?;
+    ALLOWANCES_SPENDER.save(storage, (spender, owner), &allowance)?;
+    Ok(allowance)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::testing::{message_info, mock_dependencies, mock_env, MockApi, MockQuerier, MockStorage};
+    use cosmwasm_std::{attr, from_json, Api, OwnedDeps};
+    use crate::msg::{GenesisAllocation, MinterInfo};
+    use crate::vesting::{Clock, Curve};
+
+    const TEST_METADATA_URL: &str =
+        "https://bafybeie6fkezbdf3pkioodnvuhjjhjrllcvxovhtam2z7d3qhnur4n4oy4.ipfs.w3s.link/logo.webp";
+    const MONTH: u64 = 30 * 24 * 60 * 60;
+    const YEAR: u64 = 365 * 24 * 60 * 60;
+
+    /// Builds the default instantiate message, using addresses valid for `api`:
+    /// 20% to the team, 40% + 10% gradually to the pool and 30% vested to the creator.
+    fn default_instantiate_msg(api: &MockApi) -> InstantiateMsg {
+        let now = mock_env().block.time.seconds();
+        InstantiateMsg {
+            name: "$SEINTS".to_string(),
+            symbol: "SEINTS".to_string(),
+            decimals: 6,
+            initial_supply: Uint128::new(1_000_000_000),
+            metadata_url: TEST_METADATA_URL.to_string(),
+            allocations: vec![
+                GenesisAllocation {
+                    address: api.addr_make("team").to_string(),
+                    amount: Uint128::new(200_000_000),
+                    vesting: None,
+                    revoker: None,
+                },
+                GenesisAllocation {
+                    address: api.addr_make("pool").to_string(),
+                    amount: Uint128::new(400_000_000),
+                    vesting: Some(Schedule {
+                        clock: Clock::Time,
+                        curve: Curve::Stepped {
+                            steps: vec![
+                                (now + 6 * MONTH, Uint128::new(50_000_000)),
+                                (now + 12 * MONTH, Uint128::new(75_000_000)),
+                                (now + 18 * MONTH, Uint128::new(100_000_000)),
+                            ],
+                        },
+                    }),
+                    revoker: None,
+                },
+                GenesisAllocation {
+                    address: api.addr_make("creator").to_string(),
+                    amount: Uint128::zero(),
+                    vesting: Some(Schedule {
+                        clock: Clock::Time,
+                        curve: Curve::Stepped {
+                            steps: vec![
+                                (now + YEAR, Uint128::new(100_000_000)),
+                                (now + 2 * YEAR, Uint128::new(200_000_000)),
+                                (now + 3 * YEAR, Uint128::new(300_000_000)),
+                            ],
+                        },
+                    }),
+                    revoker: None,
+                },
+            ],
+            pool_address: Some(api.addr_make("pool").to_string()),
+            treasury: Some(api.addr_make("treasury").to_string()),
+            mint: None,
+            vesting_votes: false,
+            unbonding_period: 0,
+        }
+    }
+
+    /// Instantiates the contract from the `creator` account with the default message.
+    fn setup_contract(deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>) {
+        let msg = default_instantiate_msg(&deps.api);
+        let info = message_info(&deps.api.addr_make("creator"), &[]);
+        instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+    }
+
+    /// Has the `creator` (the owner after `setup_contract`) grant `role` to `address`.
+    fn grant_role(deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>, role: Role, address: &Addr) {
+        let msg = ExecuteMsg::GrantRole {
+            role,
+            address: address.to_string(),
+        };
+        let info = message_info(&deps.api.addr_make("creator"), &[]);
+        execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    }
+
+    #[test]
+    fn proper_initialization() {
+        let mut deps = mock_dependencies();
+
+        let msg = default_instantiate_msg(&deps.api);
+        let info = message_info(&deps.api.addr_make("creator"), &[]);
+
+        let res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+        assert_eq!(0, res.messages.len());
+
+        // Verify token info
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::GetTokenInfo {}).unwrap();
+        let token_info: GetTokenInfoResponse = from_json(&res).unwrap();
+        assert_eq!("$SEINTS", token_info.name);
+        assert_eq!("SEINTS", token_info.symbol);
+        assert_eq!(6, token_info.decimals);
+        assert_eq!(Uint128::new(1_000_000_000), token_info.total_supply);
+
+        // Verify metadata URL
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::GetMetadata {}).unwrap();
+        let metadata: MetadataResponse = from_json(&res).unwrap();
+        assert_eq!(TEST_METADATA_URL, metadata.metadata_url);
+
+        // Verify balances
+        let team_balance = BALANCES.load(deps.as_ref().storage, &deps.api.addr_make("team")).unwrap();
+        assert_eq!(Uint128::new(200_000_000), team_balance); // 20% of 1 billion
+
+        let pool_balance = BALANCES.load(deps.as_ref().storage, &deps.api.addr_make("pool")).unwrap();
+        assert_eq!(Uint128::new(400_000_000), pool_balance); // 40% of 1 billion
+
+        // Verify the locked allocations
+        let vesting_info = VESTING.load(deps.as_ref().storage, &deps.api.addr_make("creator")).unwrap();
+        assert_eq!(Uint128::new(300_000_000), vesting_info.amount);
+        let pool_release_info = POOL_RELEASE_SCHEDULE.load(deps.as_ref().storage, &deps.api.addr_make("pool")).unwrap();
+        assert_eq!(Uint128::new(100_000_000), pool_release_info.amount);
+    }
+
+    #[test]
+    fn instantiate_rejects_allocations_not_matching_supply() {
+        let mut deps = mock_dependencies();
+
+        let mut msg = default_instantiate_msg(&deps.api);
+        msg.allocations[0].amount = Uint128::new(199_999_999);
+        let info = message_info(&deps.api.addr_make("creator"), &[]);
+
+        let err = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap_err();
+        assert!(matches!(
+            err,
+            ContractError::InvalidInitialSupply { expected, actual }
+                if expected == Uint128::new(999_999_999) && actual == Uint128::new(1_000_000_000)
+        ));
+    }
+
+    #[test]
+    fn instantiate_rejects_duplicate_allocations() {
+        let mut deps = mock_dependencies();
+
+        let mut msg = default_instantiate_msg(&deps.api);
+        msg.allocations[1].address = msg.allocations[0].address.clone();
+        let info = message_info(&deps.api.addr_make("creator"), &[]);
+
+        let err = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap_err();
+        assert!(matches!(err, ContractError::DuplicateAddresses { .. }));
+    }
+
+    #[test]
+    fn instantiate_rejects_invalid_schedule() {
+        let mut deps = mock_dependencies();
+
+        let mut msg = default_instantiate_msg(&deps.api);
+        msg.allocations[2].vesting = Some(Schedule {
+            clock: Clock::Time,
+            curve: Curve::Stepped {
+                steps: vec![(2 * YEAR, Uint128::new(100_000_000)), (YEAR, Uint128::new(300_000_000))],
+            },
+        });
+        let info = message_info(&deps.api.addr_make("creator"), &[]);
+
+        let err = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap_err();
+        assert!(matches!(err, ContractError::InvalidVestingSchedule {}));
+    }
+
+    // Additional tests for `transfer`, `burn`, `release_vested`, `release_pool`, and `update_metadata`...
+
+    #[test]
+    fn transfer_works() {
+        let mut deps = mock_dependencies();
+        setup_contract(&mut deps);
+
+        // Transfer tokens
+        let transfer_msg = ExecuteMsg::Transfer {
+            recipient: deps.api.addr_make("recipient").to_string(),
+            amount: Uint128::new(100),
+        };
+        let info = message_info(&deps.api.addr_make("team"), &[]);
+
+        let res = execute(deps.as_mut(), mock_env(), info, transfer_msg).unwrap();
+        let event = events::Transfer {
+            from: deps.api.addr_make("team"),
+            to: deps.api.addr_make("recipient"),
+            amount: Uint128::new(100),
+            fee: Uint128::zero(),
+            fee_treasury: Uint128::zero(),
+            fee_burned: Uint128::zero(),
+            fee_pool: Uint128::zero(),
+        };
+        assert_eq!(vec![Event::from(event)], res.events);
+
+        // Verify balances
+        let team_balance = BALANCES.load(deps.as_ref().storage, &deps.api.addr_make("team")).unwrap();
+        assert_eq!(Uint128::new(199_999_900), team_balance); // 200M - 100
+
+        let recipient_balance = BALANCES.load(deps.as_ref().storage, &deps.api.addr_make("recipient")).unwrap();
+        assert_eq!(Uint128::new(100), recipient_balance);
+    }
+
+    #[test]
+    fn burn_works() {
+        let mut deps = mock_dependencies();
+        setup_contract(&mut deps);
+
+        // Burn tokens
+        let burn_msg = ExecuteMsg::Burn {
+            amount: Uint128::new(100),
+        };
+        let info = message_info(&deps.api.addr_make("team"), &[]);
+
+        let res = execute(deps.as_mut(), mock_env(), info, burn_msg).unwrap();
+        let event = events::Burn {
+            from: deps.api.addr_make("team"),
+            amount: Uint128::new(100),
+        };
+        assert_eq!(vec![Event::from(event)], res.events);
+
+        // Verify balances and total supply
+        let team_balance = BALANCES.load(deps.as_ref().storage, &deps.api.addr_make("team")).unwrap();
+        assert_eq!(Uint128::new(199_999_900), team_balance); // 200M - 100
+
+        let token_info = TOKEN_INFO.load(deps.as_ref().storage).unwrap();
+        assert_eq!(Uint128::new(999_999_900), token_info.total_supply); // 1B - 100
+    }
+
+    #[test]
+    fn release_vested_works() {
+        let mut deps = mock_dependencies();
+        setup_contract(&mut deps);
+
+        // Advance time to the first vesting release
+        let mut env = mock_env();
+        env.block.time = env.block.time.plus_seconds(YEAR);
+
+        // Release vested tokens
+        let release_msg = ExecuteMsg::ReleaseVested {};
+        let info = message_info(&deps.api.addr_make("creator"), &[]);
+
+        let res = execute(deps.as_mut(), env.clone(), info, release_msg).unwrap();
+        let event = events::VestingReleased {
+            beneficiary: deps.api.addr_make("creator"),
+            amount: Uint128::new(100_000_000),
+        };
+        assert_eq!(vec![Event::from(event)], res.events);
+
+        // Verify balances
+        let creator_balance = BALANCES.load(deps.as_ref().storage, &deps.api.addr_make("creator")).unwrap();
+        assert_eq!(Uint128::new(100_000_000), creator_balance); // first third of 300M
+
+        // Verify vesting progress
+        let vesting_info = VESTING.load(deps.as_ref().storage, &deps.api.addr_make("creator")).unwrap();
+        assert_eq!(vesting_info.released, Uint128::new(100_000_000)); // 2 steps remaining
+
+        // Releasing again in the same block releases nothing
+        let info = message_info(&deps.api.addr_make("creator"), &[]);
+        execute(deps.as_mut(), env, info, ExecuteMsg::ReleaseVested {}).unwrap();
+        let creator_balance = BALANCES.load(deps.as_ref().storage, &deps.api.addr_make("creator")).unwrap();
+        assert_eq!(Uint128::new(100_000_000), creator_balance);
+    }
+
+    #[test]
+    fn release_vested_follows_linear_curve() {
+        let mut deps = mock_dependencies();
+
+        // Vest the creator's 300M linearly per second over 30 months after a 6 month cliff
+        let now = mock_env().block.time.seconds();
+        let mut msg = default_instantiate_msg(&deps.api);
+        msg.allocations[2].vesting = Some(Schedule {
+            clock: Clock::Time,
+            curve: Curve::CliffLinear {
+                start: now,
+                cliff: now + 6 * MONTH,
+                end: now + 30 * MONTH,
+                amount: Uint128::new(300_000_000),
+            },
+        });
+        let info = message_info(&deps.api.addr_make("creator"), &[]);
+        instantiate(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+        // Nothing before the cliff
+        let mut env = mock_env();
+        env.block.time = env.block.time.plus_seconds(6 * MONTH - 1);
+        execute(deps.as_mut(), env.clone(), info.clone(), ExecuteMsg::ReleaseVested {}).unwrap();
+        let creator_balance = BALANCES.may_load(deps.as_ref().storage, &deps.api.addr_make("creator")).unwrap();
+        assert_eq!(Uint128::zero(), creator_balance.unwrap_or_default());
+
+        // One month after the cliff, 7 of 30 months have vested
+        env.block.time = mock_env().block.time.plus_seconds(7 * MONTH);
+        execute(deps.as_mut(), env, info, ExecuteMsg::ReleaseVested {}).unwrap();
+        let creator_balance = BALANCES.load(deps.as_ref().storage, &deps.api.addr_make("creator")).unwrap();
+        assert_eq!(Uint128::new(70_000_000), creator_balance);
+    }
+
+    #[test]
+    fn release_pool_works() {
+        let mut deps = mock_dependencies();
+        setup_contract(&mut deps);
+
+        // Advance time to the first pool release
+        let mut env = mock_env();
+        env.block.time = env.block.time.plus_seconds(6 * MONTH);
+
+        // Release pool tokens
+        let release_msg = ExecuteMsg::ReleasePool {};
+        let info = message_info(&deps.api.addr_make("pool"), &[]);
+
+        let res = execute(deps.as_mut(), env.clone(), info, release_msg).unwrap();
+        let event = events::PoolReleased {
+            beneficiary: deps.api.addr_make("pool"),
+            amount: Uint128::new(50_000_000),
+        };
+        assert_eq!(vec![Event::from(event)], res.events);
+
+        // Verify balances
+        let pool_balance = BALANCES.load(deps.as_ref().storage, &deps.api.addr_make("pool")).unwrap();
+        assert_eq!(Uint128::new(450_000_000), pool_balance); // 400M (initial) + 50M (10% of 500M)
+
+        // Verify pool release progress
+        let pool_release_info = POOL_RELEASE_SCHEDULE.load(deps.as_ref().storage, &deps.api.addr_make("pool")).unwrap();
+        assert_eq!(pool_release_info.released, Uint128::new(50_000_000)); // 2 steps remaining
+    }
+
+    #[test]
+    fn update_metadata_works() {
+        let mut deps = mock_dependencies();
+        setup_contract(&mut deps);
+
+        // Update metadata
+        let update_msg = ExecuteMsg::UpdateMetadata {
+            metadata_url: "https://new-metadata-url.ipfs.w3s.link/logo.webp".to_string(),
+        };
+        let info = message_info(&deps.api.addr_make("creator"), &[]);
+
+        let res = execute(deps.as_mut(), mock_env(), info, update_msg).unwrap();
+        let event = events::MetadataUpdated {
+            metadata_url: "https://new-metadata-url.ipfs.w3s.link/logo.webp".to_string(),
+        };
+        assert_eq!(vec![Event::from(event)], res.events);
+
+        // Verify metadata URL
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::GetMetadata {}).unwrap();
+        let metadata: MetadataResponse = from_json(&res).unwrap();
+        assert_eq!("https://new-metadata-url.ipfs.w3s.link/logo.webp", metadata.metadata_url);
+    }
+
+    #[test]
+    fn increase_and_decrease_allowance_works() {
+        let mut deps = mock_dependencies();
+        setup_contract(&mut deps);
+        let team = deps.api.addr_make("team");
+        let spender = deps.api.addr_make("spender");
+
+        let info = message_info(&team, &[]);
+        let msg = ExecuteMsg::IncreaseAllowance {
+            spender: spender.to_string(),
+            amount: Uint128::new(500),
+            expires: Some(Expiration::AtHeight(mock_env().block.height + 100)),
+        };
+        execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+        let msg = ExecuteMsg::DecreaseAllowance {
+            spender: spender.to_string(),
+            amount: Uint128::new(200),
+            expires: None,
+        };
+        execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+        let query_msg = QueryMsg::Allowance {
+            owner: team.to_string(),
+            spender: spender.to_string(),
+        };
+        let res = query(deps.as_ref(), mock_env(), query_msg.clone()).unwrap();
+        let allowance: AllowanceResponse = from_json(&res).unwrap();
+        assert_eq!(Uint128::new(300), allowance.allowance);
+        assert_eq!(Expiration::AtHeight(mock_env().block.height + 100), allowance.expires);
+
+        // Decreasing past zero removes the allowance from both indexes
+        let msg = ExecuteMsg::DecreaseAllowance {
+            spender: spender.to_string(),
+            amount: Uint128::new(1_000),
+            expires: None,
+        };
+        execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+        let res = query(deps.as_ref(), mock_env(), query_msg).unwrap();
+        let allowance: AllowanceResponse = from_json(&res).unwrap();
+        assert_eq!(AllowanceResponse::default(), allowance);
+
+        let res = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::AllSpenderAllowances {
+                spender: spender.to_string(),
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+        let allowances: AllSpenderAllowancesResponse = from_json(&res).unwrap();
+        assert!(allowances.allowances.is_empty());
+    }
+
+    #[test]
+    fn cannot_grant_allowance_to_self() {
+        let mut deps = mock_dependencies();
+        setup_contract(&mut deps);
+        let team = deps.api.addr_make("team");
+
+        let msg = ExecuteMsg::IncreaseAllowance {
+            spender: team.to_string(),
+            amount: Uint128::new(500),
+            expires: None,
+        };
+        let err = execute(deps.as_mut(), mock_env(), message_info(&team, &[]), msg).unwrap_err();
+        assert!(matches!(err, ContractError::CannotSetOwnAccount {}));
+    }
+
+    #[test]
+    fn transfer_from_works() {
+        let mut deps = mock_dependencies();
+        setup_contract(&mut deps);
+        let team = deps.api.addr_make("team");
+        let spender = deps.api.addr_make("spender");
+        let recipient = deps.api.addr_make("recipient");
+
+        let msg = ExecuteMsg::IncreaseAllowance {
+            spender: spender.to_string(),
+            amount: Uint128::new(500),
+            expires: None,
+        };
+        execute(deps.as_mut(), mock_env(), message_info(&team, &[]), msg).unwrap();
+
+        let msg = ExecuteMsg::TransferFrom {
+            owner: team.to_string(),
+            recipient: recipient.to_string(),
+            amount: Uint128::new(300),
+        };
+        execute(deps.as_mut(), mock_env(), message_info(&spender, &[]), msg).unwrap();
+
+        let team_balance = BALANCES.load(deps.as_ref().storage, &team).unwrap();
+        assert_eq!(Uint128::new(199_999_700), team_balance);
+        let recipient_balance = BALANCES.load(deps.as_ref().storage, &recipient).unwrap();
+        assert_eq!(Uint128::new(300), recipient_balance);
+
+        // Only 200 of the allowance is left
+        let msg = ExecuteMsg::TransferFrom {
+            owner: team.to_string(),
+            recipient: recipient.to_string(),
+            amount: Uint128::new(300),
+        };
+        let err = execute(deps.as_mut(), mock_env(), message_info(&spender, &[]), msg).unwrap_err();
+        assert!(matches!(
+            err,
+            ContractError::InsufficientAllowance { required, available }
+                if required == Uint128::new(300) && available == Uint128::new(200)
+        ));
+    }
+
+    #[test]
+    fn transfer_from_rejects_expired_allowance() {
+        let mut deps = mock_dependencies();
+        setup_contract(&mut deps);
+        let team = deps.api.addr_make("team");
+        let spender = deps.api.addr_make("spender");
+
+        let env = mock_env();
+        let msg = ExecuteMsg::IncreaseAllowance {
+            spender: spender.to_string(),
+            amount: Uint128::new(500),
+            expires: Some(Expiration::AtTime(env.block.time.plus_seconds(60))),
+        };
+        execute(deps.as_mut(), env.clone(), message_info(&team, &[]), msg).unwrap();
+
+        let mut later = env;
+        later.block.time = later.block.time.plus_seconds(61);
+        let msg = ExecuteMsg::TransferFrom {
+            owner: team.to_string(),
+            recipient: spender.to_string(),
+            amount: Uint128::new(100),
+        };
+        let err = execute(deps.as_mut(), later, message_info(&spender, &[]), msg).unwrap_err();
+        assert!(matches!(err, ContractError::Expired {}));
+    }
+
+    #[test]
+    fn burn_from_works() {
+        let mut deps = mock_dependencies();
+        setup_contract(&mut deps);
+        let team = deps.api.addr_make("team");
+        let spender = deps.api.addr_make("spender");
+
+        let msg = ExecuteMsg::IncreaseAllowance {
+            spender: spender.to_string(),
+            amount: Uint128::new(500),
+            expires: Some(Expiration::Never {}),
+        };
+        execute(deps.as_mut(), mock_env(), message_info(&team, &[]), msg).unwrap();
+
+        let msg = ExecuteMsg::BurnFrom {
+            owner: team.to_string(),
+            amount: Uint128::new(500),
+        };
+        execute(deps.as_mut(), mock_env(), message_info(&spender, &[]), msg).unwrap();
+
+        let team_balance = BALANCES.load(deps.as_ref().storage, &team).unwrap();
+        assert_eq!(Uint128::new(199_999_500), team_balance);
+        let token_info = TOKEN_INFO.load(deps.as_ref().storage).unwrap();
+        assert_eq!(Uint128::new(999_999_500), token_info.total_supply);
+    }
+
+    #[test]
+    fn cw20_queries_work() {
+        let mut deps = mock_dependencies();
+        setup_contract(&mut deps);
+        let team = deps.api.addr_make("team");
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::Balance { address: team.to_string() }).unwrap();
+        let balance: BalanceResponse = from_json(&res).unwrap();
+        assert_eq!(Uint128::new(200_000_000), balance.balance);
+
+        // The deprecated alias still returns the bare amount
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::GetBalance { address: team.to_string() }).unwrap();
+        let legacy_balance: Uint128 = from_json(&res).unwrap();
+        assert_eq!(balance.balance, legacy_balance);
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::TokenInfo {}).unwrap();
+        let token_info: TokenInfoResponse = from_json(&res).unwrap();
+        assert_eq!(
+            TokenInfoResponse {
+                name: "$SEINTS".to_string(),
+                symbol: "SEINTS".to_string(),
+                decimals: 6,
+                total_supply: Uint128::new(1_000_000_000),
+            },
+            token_info
+        );
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::Minter {}).unwrap();
+        let minter: Option<MinterResponse> = from_json(&res).unwrap();
+        assert_eq!(None, minter);
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::MarketingInfo {}).unwrap();
+        let marketing: MarketingInfoResponse = from_json(&res).unwrap();
+        assert_eq!(Some(LogoInfo::Url(TEST_METADATA_URL.to_string())), marketing.logo);
+
+        query(deps.as_ref(), mock_env(), QueryMsg::DownloadLogo {}).unwrap_err();
+
+        let res = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::AllAccounts {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+        let accounts: AllAccountsResponse = from_json(&res).unwrap();
+        assert!(accounts.accounts.contains(&team.to_string()));
+    }
+
+    #[test]
+    fn create_vesting_works() {
+        let mut deps = mock_dependencies();
+        setup_contract(&mut deps);
+        let team = deps.api.addr_make("team");
+        grant_role(&mut deps, Role::VestingAdmin, &team);
+        let advisor = deps.api.addr_make("advisor");
+
+        // Grant the advisor 12M vesting monthly over a year
+        let now = mock_env().block.time.seconds();
+        let schedule = Schedule {
+            clock: Clock::Time,
+            curve: Curve::Stepped {
+                steps: (1..=12).map(|month| (now + month * MONTH, Uint128::new(month as u128 * 1_000_000))).collect(),
+            },
+        };
+        let msg = ExecuteMsg::CreateVesting {
+            beneficiary: advisor.to_string(),
+            schedule: schedule.clone(),
+            revoker: None,
+        };
+        execute(deps.as_mut(), mock_env(), message_info(&team, &[]), msg).unwrap();
+
+        // The tokens are escrowed from the team balance
+        let team_balance = BALANCES.load(deps.as_ref().storage, &team).unwrap();
+        assert_eq!(Uint128::new(188_000_000), team_balance);
+
+        // A second grant is rejected while the first one is still vesting
+        let msg = ExecuteMsg::CreateVesting {
+            beneficiary: advisor.to_string(),
+            schedule,
+            revoker: None,
+        };
+        let err = execute(deps.as_mut(), mock_env(), message_info(&team, &[]), msg).unwrap_err();
+        assert!(matches!(err, ContractError::VestingExists { .. }));
+
+        // After three months the advisor can release 3M
+        let mut env = mock_env();
+        env.block.time = env.block.time.plus_seconds(3 * MONTH);
+        execute(deps.as_mut(), env, message_info(&advisor, &[]), ExecuteMsg::ReleaseVested {}).unwrap();
+        let advisor_balance = BALANCES.load(deps.as_ref().storage, &advisor).unwrap();
+        assert_eq!(Uint128::new(3_000_000), advisor_balance);
+    }
+
+    #[test]
+    fn list_vesting_accounts_paginates() {
+        let mut deps = mock_dependencies();
+        setup_contract(&mut deps);
+        let team = deps.api.addr_make("team");
+        grant_role(&mut deps, Role::VestingAdmin, &team);
+
+        for name in ["alice", "bob", "carol"] {
+            let msg = ExecuteMsg::CreateVesting {
+                beneficiary: deps.api.addr_make(name).to_string(),
+                schedule: Schedule {
+                    clock: Clock::Height,
+                    curve: Curve::Stepped {
+                        steps: vec![(mock_env().block.height + 100, Uint128::new(1_000))],
+                    },
+                },
+                revoker: None,
+            };
+            execute(deps.as_mut(), mock_env(), message_info(&team, &[]), msg).unwrap();
+        }
+
+        // The creator's genesis vesting plus the three new records
+        let res = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::ListVestingAccounts {
+                start_after: None,
+                limit: Some(2),
+            },
+        )
+        .unwrap();
+        let first_page: VestingAccountsResponse = from_json(&res).unwrap();
+        assert_eq!(2, first_page.accounts.len());
+
+        let res = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::ListVestingAccounts {
+                start_after: Some(first_page.accounts[1].address.clone()),
+                limit: None,
+            },
+        )
+        .unwrap();
+        let second_page: VestingAccountsResponse = from_json(&res).unwrap();
+        assert_eq!(2, second_page.accounts.len());
+        assert!(second_page.accounts.iter().all(|a| !first_page.accounts.contains(a)));
+    }
+
+    #[test]
+    fn revoke_vesting_claws_back_unvested() {
+        let mut deps = mock_dependencies();
+        setup_contract(&mut deps);
+        let team = deps.api.addr_make("team");
+        grant_role(&mut deps, Role::VestingAdmin, &team);
+        let employee = deps.api.addr_make("employee");
+        let treasury = deps.api.addr_make("treasury");
+
+        // 1,000 vesting linearly over 10 months, revocable by the team
+        let now = mock_env().block.time.seconds();
+        let msg = ExecuteMsg::CreateVesting {
+            beneficiary: employee.to_string(),
+            schedule: Schedule {
+                clock: Clock::Time,
+                curve: Curve::CliffLinear {
+                    start: now,
+                    cliff: now,
+                    end: now + 10 * MONTH,
+                    amount: Uint128::new(1_000),
+                },
+            },
+            revoker: Some(team.to_string()),
+        };
+        execute(deps.as_mut(), mock_env(), message_info(&team, &[]), msg).unwrap();
+
+        // The employee releases 2 months, then leaves after 4 months
+        let mut env = mock_env();
+        env.block.time = env.block.time.plus_seconds(2 * MONTH);
+        execute(deps.as_mut(), env.clone(), message_info(&employee, &[]), ExecuteMsg::ReleaseVested {}).unwrap();
+        env.block.time = mock_env().block.time.plus_seconds(4 * MONTH);
+
+        // Only the revoker may revoke
+        let msg = ExecuteMsg::RevokeVesting {
+            beneficiary: employee.to_string(),
+        };
+        let err = execute(deps.as_mut(), env.clone(), message_info(&employee, &[]), msg.clone()).unwrap_err();
+        assert!(matches!(err, ContractError::Unauthorized {}));
+
+        // Revoking waits while vesting releases are paused
+        let creator = deps.api.addr_make("creator");
+        let pause = ExecuteMsg::Pause { operations: Some(vec![Operation::VestingReleases]) };
+        execute(deps.as_mut(), env.clone(), message_info(&creator, &[]), pause).unwrap();
+        let err = execute(deps.as_mut(), env.clone(), message_info(&team, &[]), msg.clone()).unwrap_err();
+        assert!(matches!(err, ContractError::Paused { operation } if operation == "vesting_releases"));
+        let unpause = ExecuteMsg::Unpause { operations: Some(vec![Operation::VestingReleases]) };
+        execute(deps.as_mut(), env.clone(), message_info(&creator, &[]), unpause).unwrap();
+
+        // Freezing the employee does not block the revocation
+        let freeze = ExecuteMsg::FreezeAccount {
+            address: employee.to_string(),
+            reason: "left the company".to_string(),
+        };
+        execute(deps.as_mut(), env.clone(), message_info(&creator, &[]), freeze).unwrap();
+
+        let res = execute(deps.as_mut(), env.clone(), message_info(&team, &[]), msg.clone()).unwrap();
+        let event = events::VestingRevoked {
+            beneficiary: employee.clone(),
+            revoker: team.clone(),
+            paid_out: Uint128::new(200),
+            clawed_back: Uint128::new(600),
+            treasury: treasury.clone(),
+        };
+        assert_eq!(vec![Event::from(event)], res.events);
+
+        let employee_balance = BALANCES.load(deps.as_ref().storage, &employee).unwrap();
+        assert_eq!(Uint128::new(400), employee_balance);
+        let treasury_balance = BALANCES.load(deps.as_ref().storage, &treasury).unwrap();
+        assert_eq!(Uint128::new(600), treasury_balance);
+
+        // The record is closed and cannot be revoked twice
+        let err = execute(deps.as_mut(), env, message_info(&team, &[]), msg).unwrap_err();
+        assert!(matches!(err, ContractError::NotRevocable { .. }));
+    }
+
+    #[test]
+    fn claimable_and_projection_match_release() {
+        let mut deps = mock_dependencies();
+        setup_contract(&mut deps);
+        let creator = deps.api.addr_make("creator");
+        let pool = deps.api.addr_make("pool");
+
+        // Halfway through the second year the creator can claim the first step
+        let mut env = mock_env();
+        env.block.time = env.block.time.plus_seconds(YEAR + YEAR / 2);
+        let res = query(deps.as_ref(), env.clone(), QueryMsg::Claimable { address: creator.to_string() }).unwrap();
+        let claimable: ClaimableResponse = from_json(&res).unwrap();
+        let status = claimable.vesting.unwrap();
+        assert_eq!(Uint128::new(100_000_000), status.claimable_now);
+        assert_eq!(Uint128::zero(), status.total_released);
+        assert_eq!(Uint128::new(200_000_000), status.still_locked);
+        assert_eq!(Some(mock_env().block.time.seconds() + 2 * YEAR), status.next_unlock_time);
+        assert_eq!(None, claimable.pool);
+
+        // Releasing moves exactly the claimable amount
+        execute(deps.as_mut(), env.clone(), message_info(&creator, &[]), ExecuteMsg::ReleaseVested {}).unwrap();
+        let creator_balance = BALANCES.load(deps.as_ref().storage, &creator).unwrap();
+        assert_eq!(status.claimable_now, creator_balance);
+
+        // Projecting the pool to the end of its schedule
+        let at_time = mock_env().block.time.seconds() + 18 * MONTH;
+        let res = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::VestingProjection {
+                address: pool.to_string(),
+                at_time,
+                at_height: None,
+            },
+        )
+        .unwrap();
+        let projection: VestingProjectionResponse = from_json(&res).unwrap();
+        let pool_status = projection.pool.unwrap();
+        assert_eq!(Uint128::new(100_000_000), pool_status.claimable_now);
+        assert_eq!(Uint128::zero(), pool_status.still_locked);
+        assert_eq!(None, pool_status.next_unlock_time);
+        assert_eq!(Uint128::new(500_000_000), projection.projected_balance);
+    }
+
+    #[test]
+    fn release_for_pays_beneficiary_and_keeper() {
+        let mut deps = mock_dependencies();
+        setup_contract(&mut deps);
+        let creator = deps.api.addr_make("creator");
+        let team = deps.api.addr_make("team");
+        let pool = deps.api.addr_make("pool");
+        let keeper = deps.api.addr_make("keeper");
+
+        // Only the owner sets the tip, and anyone can fund the reserve
+        let msg = ExecuteMsg::UpdateKeeperTip {
+            tip: Uint128::new(10),
+            min_release: Uint128::new(1_000),
+        };
+        let err = execute(deps.as_mut(), mock_env(), message_info(&team, &[]), msg.clone()).unwrap_err();
+        assert!(matches!(err, ContractError::Unauthorized {}));
+        execute(deps.as_mut(), mock_env(), message_info(&creator, &[]), msg).unwrap();
+        let msg = ExecuteMsg::FundKeeperReserve { amount: Uint128::new(15) };
+        execute(deps.as_mut(), mock_env(), message_info(&team, &[]), msg).unwrap();
+
+        // The keeper pushes the pool's first unlock
+        let mut env = mock_env();
+        env.block.time = env.block.time.plus_seconds(6 * MONTH);
+        let msg = ExecuteMsg::ReleaseFor { beneficiary: pool.to_string() };
+        execute(deps.as_mut(), env.clone(), message_info(&keeper, &[]), msg.clone()).unwrap();
+        let pool_balance = BALANCES.load(deps.as_ref().storage, &pool).unwrap();
+        assert_eq!(Uint128::new(450_000_000), pool_balance);
+        let keeper_balance = BALANCES.load(deps.as_ref().storage, &keeper).unwrap();
+        assert_eq!(Uint128::new(10), keeper_balance);
+
+        // Nothing left to release, so no tip
+        let res = execute(deps.as_mut(), env, message_info(&keeper, &[]), msg).unwrap();
+        let event = events::ReleasedFor {
+            keeper: keeper.clone(),
+            beneficiary: pool.clone(),
+            amount: Uint128::zero(),
+            tip: Uint128::zero(),
+        };
+        assert_eq!(vec![Event::from(event)], res.events);
+
+        // Unknown beneficiaries are rejected
+        let msg = ExecuteMsg::ReleaseFor { beneficiary: keeper.to_string() };
+        let err = execute(deps.as_mut(), mock_env(), message_info(&keeper, &[]), msg).unwrap_err();
+        assert!(matches!(err, ContractError::NoVesting { .. }));
+    }
+
+    #[test]
+    fn release_batch_caps_tip_at_reserve() {
+        let mut deps = mock_dependencies();
+        setup_contract(&mut deps);
+        let creator = deps.api.addr_make("creator");
+        let team = deps.api.addr_make("team");
+        let pool = deps.api.addr_make("pool");
+        let keeper = deps.api.addr_make("keeper");
+
+        let msg = ExecuteMsg::UpdateKeeperTip {
+            tip: Uint128::new(10),
+            min_release: Uint128::new(1_000),
+        };
+        execute(deps.as_mut(), mock_env(), message_info(&creator, &[]), msg).unwrap();
+        let msg = ExecuteMsg::FundKeeperReserve { amount: Uint128::new(15) };
+        execute(deps.as_mut(), mock_env(), message_info(&team, &[]), msg).unwrap();
+
+        let mut env = mock_env();
+        env.block.time = env.block.time.plus_seconds(YEAR);
+        let msg = ExecuteMsg::ReleaseBatch {
+            beneficiaries: vec![pool.to_string(), creator.to_string()],
+        };
+        execute(deps.as_mut(), env, message_info(&keeper, &[]), msg).unwrap();
+
+        assert_eq!(Uint128::new(475_000_000), BALANCES.load(deps.as_ref().storage, &pool).unwrap());
+        assert_eq!(Uint128::new(100_000_000), BALANCES.load(deps.as_ref().storage, &creator).unwrap());
+        // Two releases earn 20, but only 15 were in the reserve
+        assert_eq!(Uint128::new(15), BALANCES.load(deps.as_ref().storage, &keeper).unwrap());
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::KeeperInfo {}).unwrap();
+        let keeper_info: KeeperInfoResponse = from_json(&res).unwrap();
+        assert_eq!(Uint128::new(10), keeper_info.tip);
+        assert_eq!(Uint128::new(1_000), keeper_info.min_release);
+        assert_eq!(Uint128::zero(), keeper_info.reserve);
+    }
+
+    #[test]
+    fn release_batch_skips_blocked_beneficiaries_and_tips_no_dust() {
+        let mut deps = mock_dependencies();
+        setup_contract(&mut deps);
+        let creator = deps.api.addr_make("creator");
+        let team = deps.api.addr_make("team");
+        let pool = deps.api.addr_make("pool");
+        let keeper = deps.api.addr_make("keeper");
+        let employee = deps.api.addr_make("employee");
+
+        let msg = ExecuteMsg::UpdateKeeperTip {
+            tip: Uint128::new(10),
+            min_release: Uint128::new(1_000),
+        };
+        execute(deps.as_mut(), mock_env(), message_info(&creator, &[]), msg).unwrap();
+        let msg = ExecuteMsg::FundKeeperReserve { amount: Uint128::new(100) };
+        execute(deps.as_mut(), mock_env(), message_info(&team, &[]), msg).unwrap();
+
+        // The employee vests only 100 tokens, below the minimum release that earns a tip
+        grant_role(&mut deps, Role::VestingAdmin, &team);
+        let now = mock_env().block.time.seconds();
+        let msg = ExecuteMsg::CreateVesting {
+            beneficiary: employee.to_string(),
+            schedule: Schedule {
+                clock: Clock::Time,
+                curve: Curve::CliffLinear {
+                    start: now,
+                    cliff: now,
+                    end: now + MONTH,
+                    amount: Uint128::new(100),
+                },
+            },
+            revoker: None,
+        };
+        execute(deps.as_mut(), mock_env(), message_info(&team, &[]), msg).unwrap();
+        let msg = ExecuteMsg::FreezeAccount {
+            address: creator.to_string(),
+            reason: "lost keys".to_string(),
+        };
+        execute(deps.as_mut(), mock_env(), message_info(&creator, &[]), msg).unwrap();
+
+        // The frozen creator and the keeper without a record are skipped, the rest is released
+        let mut env = mock_env();
+        env.block.time = env.block.time.plus_seconds(YEAR);
+        let msg = ExecuteMsg::ReleaseBatch {
+            beneficiaries: vec![pool.to_string(), employee.to_string(), creator.to_string(), keeper.to_string()],
+        };
+        let res = execute(deps.as_mut(), env.clone(), message_info(&keeper, &[]), msg).unwrap();
+        let batch = events::BatchReleased {
+            keeper: keeper.clone(),
+            beneficiaries: 4,
+            released: 2,
+            skipped: 2,
+            amount: Uint128::new(75_000_100),
+            tip: Uint128::new(10),
+        };
+        let frozen = events::ReleaseSkipped {
+            keeper: keeper.clone(),
+            beneficiary: creator.clone(),
+            reason: "frozen".to_string(),
+        };
+        let no_record = events::ReleaseSkipped {
+            keeper: keeper.clone(),
+            beneficiary: keeper.clone(),
+            reason: "no_record".to_string(),
+        };
+        assert_eq!(vec![Event::from(batch), Event::from(frozen), Event::from(no_record)], res.events);
+        assert_eq!(Uint128::new(100), BALANCES.load(deps.as_ref().storage, &employee).unwrap());
+        assert_eq!(Uint128::new(10), BALANCES.load(deps.as_ref().storage, &keeper).unwrap());
+
+        // Beneficiaries whose releases are paused are skipped too
+        let msg = ExecuteMsg::Pause { operations: Some(vec![Operation::PoolReleases]) };
+        execute(deps.as_mut(), env.clone(), message_info(&creator, &[]), msg).unwrap();
+        let msg = ExecuteMsg::ReleaseBatch { beneficiaries: vec![pool.to_string()] };
+        let res = execute(deps.as_mut(), env, message_info(&keeper, &[]), msg).unwrap();
+        let paused = events::ReleaseSkipped {
+            keeper: keeper.clone(),
+            beneficiary: pool.clone(),
+            reason: "paused".to_string(),
+        };
+        assert_eq!(Event::from(paused), res.events[1]);
+    }
+
+    #[test]
+    fn release_batch_rejects_duplicates_and_oversized_batches() {
+        let mut deps = mock_dependencies();
+        setup_contract(&mut deps);
+        let creator = deps.api.addr_make("creator");
+        let pool = deps.api.addr_make("pool");
+        let keeper = deps.api.addr_make("keeper");
+
+        let mut env = mock_env();
+        env.block.time = env.block.time.plus_seconds(YEAR);
+        let msg = ExecuteMsg::ReleaseBatch { beneficiaries: vec![] };
+        let err = execute(deps.as_mut(), env.clone(), message_info(&keeper, &[]), msg).unwrap_err();
+        assert!(matches!(err, ContractError::EmptyBatch {}));
+        let msg = ExecuteMsg::ReleaseBatch {
+            beneficiaries: vec![pool.to_string(), pool.to_string()],
+        };
+        let err = execute(deps.as_mut(), env.clone(), message_info(&keeper, &[]), msg).unwrap_err();
+        assert!(matches!(err, ContractError::DuplicateAddresses { .. }));
+
+        // The batch size limit of batch transfers applies here too
+        let msg = ExecuteMsg::UpdateMaxBatchSize { max_batch_size: 1 };
+        execute(deps.as_mut(), mock_env(), message_info(&creator, &[]), msg).unwrap();
+        let msg = ExecuteMsg::ReleaseBatch {
+            beneficiaries: vec![pool.to_string(), creator.to_string()],
+        };
+        let err = execute(deps.as_mut(), env, message_info(&keeper, &[]), msg).unwrap_err();
+        assert!(matches!(err, ContractError::BatchTooLarge { size: 2, max: 1 }));
+        assert_eq!(Uint128::new(400_000_000), BALANCES.load(deps.as_ref().storage, &pool).unwrap());
+    }
+
+    #[test]
+    fn migrate_converts_legacy_layout() {
+        let mut deps = mock_dependencies();
+        let owner = deps.api.addr_make("owner");
+
+        // What the legacy contract stored: token info, balances and its own contract name
+        set_contract_version(deps.as_mut().storage, LEGACY_CONTRACT_NAME, "0.1.0").unwrap();
+        let token_info = TokenInfo {
+            name: "$SEINTS".to_string(),
+            symbol: "SEINTS".to_string(),
+            decimals: 6,
+            total_supply: Uint128::new(1_000),
+            owner: Some(owner.clone()),
+            mint: None,
+        };
+        TOKEN_INFO.save(deps.as_mut().storage, &token_info).unwrap();
+        BALANCES
+            .save(deps.as_mut().storage, &owner, &Uint128::new(1_000), mock_env().block.height)
+            .unwrap();
+
+        let msg = MigrateMsg {
+            metadata_url: Some(TEST_METADATA_URL.to_string()),
+            ..MigrateMsg::default()
+        };
+        let res = migrate(deps.as_mut(), mock_env(), msg).unwrap();
+        let event = events::Migrated {
+            from_contract: LEGACY_CONTRACT_NAME.to_string(),
+            from_version: "0.1.0".to_string(),
+            to_version: CONTRACT_VERSION.to_string(),
+        };
+        assert_eq!(vec![Event::from(event)], res.events);
+
+        let version = get_contract_version(deps.as_ref().storage).unwrap();
+        assert_eq!(CONTRACT_NAME, version.contract);
+        assert_eq!(CONTRACT_VERSION, version.version);
+        let config = CONFIG.load(deps.as_ref().storage).unwrap();
+        assert_eq!(owner, config.treasury);
+        assert_eq!(None, config.pool);
+        assert_eq!(TEST_METADATA_URL, METADATA_URL.load(deps.as_ref().storage).unwrap());
+        assert_eq!(Uint128::new(1_000), BALANCES.load(deps.as_ref().storage, &owner).unwrap());
+        assert!(ROLES.has(deps.as_ref().storage, (Role::MetadataManager.as_str(), &owner)));
+        assert_eq!(Some(Uint128::new(1_000)), TOTAL_SUPPLY.may_load(deps.as_ref().storage).unwrap());
+    }
 
     #[test]
-    fn proper_initialization() {
+    fn migrate_converts_v0_1_vesting_records() {
         let mut deps = mock_dependencies();
+        let creator = deps.api.addr_make("creator");
+        let pool = deps.api.addr_make("pool");
+        let now = mock_env().block.time;
 
-        let msg = InstantiateMsg {
+        // 0.1.0 keyed records by (beneficiary, None, None) and kept only the steps still locked
+        fn save_v0_1_record(storage: &mut dyn Storage, namespace: &str, beneficiary: &Addr, value: &str) {
+            let mut key = (namespace.len() as u16).to_be_bytes().to_vec();
+            key.extend(namespace.as_bytes());
+            key.extend((beneficiary.as_str().len() as u16).to_be_bytes());
+            key.extend(beneficiary.as_bytes());
+            storage.set(&key, value.as_bytes());
+        }
+        set_contract_version(deps.as_mut().storage, CONTRACT_NAME, "0.1.0").unwrap();
+        let token_info = TokenInfo {
             name: "$SEINTS".to_string(),
             symbol: "SEINTS".to_string(),
             decimals: 6,
-            initial_supply: Uint128::new(1_000_000_000),
-            metadata_url: "https://bafybeie6fkezbdf3pkioodnvuhjjhjrllcvxovhtam2z7d3qhnur4n4oy4.ipfs.w3s.link/logo.webp".to_string(),
-            team_address: "team".to_string(),
-            pool_address: "pool".to_string(),
+            total_supply: Uint128::new(800_000_000),
+            owner: Some(creator.clone()),
+            mint: None,
         };
+        TOKEN_INFO.save(deps.as_mut().storage, &token_info).unwrap();
+        // The first year of the creator's vesting was already released
+        save_v0_1_record(
+            deps.as_mut().storage,
+            "vesting",
+            &creator,
+            &format!(
+                r#"{{"amount":"200000000","start_time":"{}","release_schedule":[["{}","100000000"],["{}","100000000"]]}}"#,
+                now.nanos(),
+                now.plus_seconds(2 * YEAR).nanos(),
+                now.plus_seconds(3 * YEAR).nanos(),
+            ),
+        );
+        // The steps leave 50M uncovered, which unlocks with the last one
+        save_v0_1_record(
+            deps.as_mut().storage,
+            "pool_release_schedule",
+            &pool,
+            &format!(
+                r#"{{"amount":"225000000","release_schedule":[["{}","100000000"],["{}","75000000"]]}}"#,
+                now.plus_seconds(18 * MONTH).nanos(),
+                now.plus_seconds(12 * MONTH).nanos(),
+            ),
+        );
+
+        migrate(deps.as_mut(), mock_env(), MigrateMsg::default()).unwrap();
+
+        let config = CONFIG.load(deps.as_ref().storage).unwrap();
+        assert_eq!(Some(pool.clone()), config.pool);
+        assert_eq!(creator, config.treasury);
+        let vesting_info = VESTING.load(deps.as_ref().storage, &creator).unwrap();
+        assert_eq!(Uint128::new(200_000_000), vesting_info.amount);
+        assert_eq!(Uint128::zero(), vesting_info.released);
+        assert_eq!(now, vesting_info.start_time);
+        assert_eq!(
+            Curve::Stepped {
+                steps: vec![
+                    (now.seconds() + 2 * YEAR, Uint128::new(100_000_000)),
+                    (now.seconds() + 3 * YEAR, Uint128::new(200_000_000)),
+                ],
+            },
+            vesting_info.schedule.curve
+        );
+        let pool_release_info = POOL_RELEASE_SCHEDULE.load(deps.as_ref().storage, &pool).unwrap();
+        assert_eq!(now.plus_seconds(12 * MONTH), pool_release_info.start_time);
+        assert_eq!(
+            Curve::Stepped {
+                steps: vec![
+                    (now.seconds() + 12 * MONTH, Uint128::new(75_000_000)),
+                    (now.seconds() + 18 * MONTH, Uint128::new(225_000_000)),
+                ],
+            },
+            pool_release_info.schedule.curve
+        );
+        // The legacy keys are gone, only the converted records remain
+        let records = deps
+            .as_ref()
+            .storage
+            .range(None, None, Order::Ascending)
+            .filter(|(key, _)| key.starts_with(b"\0\x07vesting") || key.starts_with(b"\0\x15pool_release_schedule"))
+            .count();
+        assert_eq!(2, records);
 
-        let info = MessageInfo {
-            sender: Addr::unchecked("creator"),
-            funds: coins(1000, "earth"),
+        // Converted records release on the new schedule
+        let mut env = mock_env();
+        env.block.time = now.plus_seconds(2 * YEAR);
+        execute(deps.as_mut(), env.clone(), message_info(&creator, &[]), ExecuteMsg::ReleaseVested {}).unwrap();
+        assert_eq!(Uint128::new(100_000_000), BALANCES.load(deps.as_ref().storage, &creator).unwrap());
+        execute(deps.as_mut(), env, message_info(&pool, &[]), ExecuteMsg::ReleasePool {}).unwrap();
+        assert_eq!(Uint128::new(225_000_000), BALANCES.load(deps.as_ref().storage, &pool).unwrap());
+    }
+
+    #[test]
+    fn migrate_rejects_downgrades_and_foreign_contracts() {
+        let mut deps = mock_dependencies();
+        setup_contract(&mut deps);
+
+        // Re-running the current version is allowed
+        migrate(deps.as_mut(), mock_env(), MigrateMsg::default()).unwrap();
+
+        set_contract_version(deps.as_mut().storage, CONTRACT_NAME, "99.0.0").unwrap();
+        let err = migrate(deps.as_mut(), mock_env(), MigrateMsg::default()).unwrap_err();
+        assert!(matches!(err, ContractError::CannotDowngrade { .. }));
+
+        set_contract_version(deps.as_mut().storage, "crates.io:cw20-base", "0.1.0").unwrap();
+        let err = migrate(deps.as_mut(), mock_env(), MigrateMsg::default()).unwrap_err();
+        assert!(matches!(err, ContractError::WrongContract { .. }));
+    }
+
+    #[test]
+    fn two_step_ownership_transfer() {
+        let mut deps = mock_dependencies();
+        setup_contract(&mut deps);
+        let creator = deps.api.addr_make("creator");
+        let dao = deps.api.addr_make("dao");
+        let typo = deps.api.addr_make("typo");
+
+        // A mistyped proposal can be replaced before anyone accepts it
+        let msg = ExecuteMsg::ProposeNewOwner {
+            new_owner: typo.to_string(),
+            expiry: None,
+        };
+        execute(deps.as_mut(), mock_env(), message_info(&creator, &[]), msg).unwrap();
+        let msg = ExecuteMsg::ProposeNewOwner {
+            new_owner: dao.to_string(),
+            expiry: Some(Expiration::AtHeight(mock_env().block.height + 100)),
         };
+        execute(deps.as_mut(), mock_env(), message_info(&creator, &[]), msg).unwrap();
 
-        let res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
-        assert_eq!(0, res.messages.len());
+        let err = execute(deps.as_mut(), mock_env(), message_info(&typo, &[]), ExecuteMsg::AcceptOwnership {})
+            .unwrap_err();
+        assert!(matches!(err, ContractError::Unauthorized {}));
 
-        // Verify token info
-        let res = query(deps.as_ref(), mock_env(), QueryMsg::GetTokenInfo {}).unwrap();
-        let token_info: TokenInfoResponse = from_binary(&res).unwrap();
-        assert_eq!("$SEINTS", token_info.name);
-        assert_eq!("SEINTS", token_info.symbol);
-        assert_eq!(6, token_info.decimals);
-        assert_eq!(Uint128::new(1_000_000_000), token_info.total_supply);
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::PendingOwner {}).unwrap();
+        let pending: PendingOwnerResponse = from_json(&res).unwrap();
+        assert_eq!(Some(creator.to_string()), pending.owner);
+        assert_eq!(Some(dao.to_string()), pending.pending_owner);
 
-        // Verify metadata URL
-        let res = query(deps.as_ref(), mock_env(), QueryMsg::GetMetadata {}).unwrap();
-        let metadata: MetadataResponse = from_binary(&res).unwrap();
-        assert_eq!(
-            "https://bafybeie6fkezbdf3pkioodnvuhjjhjrllcvxovhtam2z7d3qhnur4n4oy4.ipfs.w3s.link/logo.webp",
-            metadata.metadata_url
-        );
+        // The proposal cannot be accepted once expired
+        let mut env = mock_env();
+        env.block.height += 100;
+        let err = execute(deps.as_mut(), env, message_info(&dao, &[]), ExecuteMsg::AcceptOwnership {}).unwrap_err();
+        assert!(matches!(err, ContractError::OwnershipTransferExpired {}));
 
-        // Verify balances
-        let team_balance = BALANCES.load(deps.as_ref().storage, &Addr::unchecked("team")).unwrap();
-        assert_eq!(Uint128::new(200_000_000), team_balance); // 20% of 1 billion
+        execute(deps.as_mut(), mock_env(), message_info(&dao, &[]), ExecuteMsg::AcceptOwnership {}).unwrap();
+        let token_info = TOKEN_INFO.load(deps.as_ref().storage).unwrap();
+        assert_eq!(Some(dao.clone()), token_info.owner);
+        assert!(!PENDING_OWNER.exists(deps.as_ref().storage));
 
-        let pool_balance = BALANCES.load(deps.as_ref().storage, &Addr::unchecked("pool")).unwrap();
-        assert_eq!(Uint128::new(400_000_000), pool_balance); // 40% of 1 billion
+        // The previous owner can no longer act as owner
+        let msg = ExecuteMsg::GrantRole {
+            role: Role::Pauser,
+            address: typo.to_string(),
+        };
+        let err = execute(deps.as_mut(), mock_env(), message_info(&creator, &[]), msg).unwrap_err();
+        assert!(matches!(err, ContractError::Unauthorized {}));
     }
 
-    // Additional tests for `transfer`, `burn`, `release_vested`, `release_pool`, and `update_metadata`...
-}
+    #[test]
+    fn renounce_ownership_clears_pending_transfer() {
+        let mut deps = mock_dependencies();
+        setup_contract(&mut deps);
+        let creator = deps.api.addr_make("creator");
+        let dao = deps.api.addr_make("dao");
+
+        let msg = ExecuteMsg::ProposeNewOwner {
+            new_owner: dao.to_string(),
+            expiry: None,
+        };
+        execute(deps.as_mut(), mock_env(), message_info(&creator, &[]), msg).unwrap();
+        execute(deps.as_mut(), mock_env(), message_info(&creator, &[]), ExecuteMsg::RenounceOwnership {}).unwrap();
+
+        let err = execute(deps.as_mut(), mock_env(), message_info(&dao, &[]), ExecuteMsg::AcceptOwnership {})
+            .unwrap_err();
+        assert!(matches!(err, ContractError::NoPendingOwner {}));
+        let err = execute(deps.as_mut(), mock_env(), message_info(&creator, &[]), ExecuteMsg::RenounceOwnership {})
+            .unwrap_err();
+        assert!(matches!(err, ContractError::Unauthorized {}));
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::PendingOwner {}).unwrap();
+        let pending: PendingOwnerResponse = from_json(&res).unwrap();
+        assert_eq!(None, pending.owner);
+        assert_eq!(None, pending.pending_owner);
+    }
 
     #[test]
-    fn transfer_works() {
+    fn roles_gate_privileged_actions() {
         let mut deps = mock_dependencies();
+        setup_contract(&mut deps);
+        let creator = deps.api.addr_make("creator");
+        let ops = deps.api.addr_make("ops");
 
-        // Instantiate the contract
-        let msg = InstantiateMsg {
-            name: "$SEINTS".to_string(),
-            symbol: "SEINTS".to_string(),
-            decimals: 6,
-            initial_supply: Uint128::new(1_000_000_000),
-            metadata_url: "https://bafybeie6fkezbdf3pkioodnvuhjjhjrllcvxovhtam2z7d3qhnur4n4oy4.ipfs.w3s.link/logo.webp".to_string(),
-            team_address: "team".to_string(),
-            pool_address: "pool".to_string(),
+        // The instantiator holds every role
+        for role in Role::ALL {
+            let res = query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::HasRole {
+                    role,
+                    address: creator.to_string(),
+                },
+            )
+            .unwrap();
+            let has_role: HasRoleResponse = from_json(&res).unwrap();
+            assert!(has_role.has_role);
+        }
+
+        // Only the owner hands out roles
+        let msg = ExecuteMsg::GrantRole {
+            role: Role::MetadataManager,
+            address: ops.to_string(),
         };
+        let err = execute(deps.as_mut(), mock_env(), message_info(&ops, &[]), msg).unwrap_err();
+        assert!(matches!(err, ContractError::Unauthorized {}));
 
-        // Use MessageInfo instead of mock_info
-        let info = MessageInfo {
-            sender: Addr::unchecked("creator"),
-            funds: coins(1000, "earth"),
+        let update = ExecuteMsg::UpdateMetadata {
+            metadata_url: "https://example.com/new.webp".to_string(),
         };
+        let err = execute(deps.as_mut(), mock_env(), message_info(&ops, &[]), update.clone()).unwrap_err();
+        assert!(matches!(err, ContractError::MissingRole { role } if role == "metadata_manager"));
 
-        instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+        grant_role(&mut deps, Role::MetadataManager, &ops);
+        execute(deps.as_mut(), mock_env(), message_info(&ops, &[]), update.clone()).unwrap();
 
-        // Transfer tokens
-        let transfer_msg = ExecuteMsg::Transfer {
-            recipient: "recipient".to_string(),
-            amount: Uint128::new(100),
+        let res = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::ListRoleMembers {
+                role: Role::MetadataManager,
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+        let members: RoleMembersResponse = from_json(&res).unwrap();
+        assert_eq!(2, members.members.len());
+        assert!(members.members.contains(&ops.to_string()));
+
+        // Revoking the role takes the privilege away again
+        let msg = ExecuteMsg::RevokeRole {
+            role: Role::MetadataManager,
+            address: ops.to_string(),
         };
+        execute(deps.as_mut(), mock_env(), message_info(&creator, &[]), msg).unwrap();
+        let err = execute(deps.as_mut(), mock_env(), message_info(&ops, &[]), update).unwrap_err();
+        assert!(matches!(err, ContractError::MissingRole { .. }));
+    }
+
+    #[test]
+    fn mint_respects_cap_and_schedule() {
+        let mut deps = mock_dependencies();
+        let creator = deps.api.addr_make("creator");
+        let minter = deps.api.addr_make("minter");
+        let recipient = deps.api.addr_make("recipient");
+
+        // 100M more can be minted in total, 10M per month
+        let now = mock_env().block.time.seconds();
+        let mut msg = default_instantiate_msg(&deps.api);
+        msg.mint = Some(MinterInfo {
+            minter: minter.to_string(),
+            cap: Some(Uint128::new(1_100_000_000)),
+            schedule: Some(Schedule {
+                clock: Clock::Time,
+                curve: Curve::CliffLinear {
+                    start: now,
+                    cliff: now,
+                    end: now + 10 * MONTH,
+                    amount: Uint128::new(1_000_000_000),
+                },
+            }),
+        });
+        instantiate(deps.as_mut(), mock_env(), message_info(&creator, &[]), msg).unwrap();
 
-        // Use MessageInfo instead of mock_info
-        let info = MessageInfo {
-            sender: Addr::unchecked("creator"),
-            funds: vec![],
+        let mut env = mock_env();
+        env.block.time = env.block.time.plus_seconds(MONTH);
+        let mint = |amount: u128| ExecuteMsg::Mint {
+            recipient: recipient.to_string(),
+            amount: Uint128::new(amount),
         };
 
-        let res = execute(deps.as_mut(), mock_env(), info, transfer_msg).unwrap();
-        assert_eq!(res.attributes.len(), 4);
+        // Only the minter may mint
+        let err = execute(deps.as_mut(), env.clone(), message_info(&creator, &[]), mint(1)).unwrap_err();
+        assert!(matches!(err, ContractError::Unauthorized {}));
 
-        // Verify balances
-        let creator_balance = BALANCES.load(deps.as_ref().storage, &Addr::unchecked("creator")).unwrap();
-        assert_eq!(Uint128::new(299_999_900), creator_balance); // 300M - 100
+        // The schedule allows 100M after a month
+        let err = execute(deps.as_mut(), env.clone(), message_info(&minter, &[]), mint(100_000_001)).unwrap_err();
+        assert!(matches!(err, ContractError::MintLimitExceeded { .. }));
+        let res = execute(deps.as_mut(), env.clone(), message_info(&minter, &[]), mint(100_000_000)).unwrap();
+        let event = events::Mint {
+            minter: minter.clone(),
+            to: recipient.clone(),
+            amount: Uint128::new(100_000_000),
+        };
+        assert_eq!(vec![Event::from(event)], res.events);
+        assert_eq!(Uint128::new(100_000_000), BALANCES.load(deps.as_ref().storage, &recipient).unwrap());
 
-        let recipient_balance = BALANCES.load(deps.as_ref().storage, &Addr::unchecked("recipient")).unwrap();
-        assert_eq!(Uint128::new(100), recipient_balance);
+        // Much later the schedule would allow more, but the cap is reached
+        env.block.time = env.block.time.plus_seconds(YEAR);
+        let res = query(deps.as_ref(), env.clone(), QueryMsg::Minter {}).unwrap();
+        let minter_info: Option<MinterResponse> = from_json(&res).unwrap();
+        assert_eq!(Some(Uint128::zero()), minter_info.unwrap().mintable);
+        let err = execute(deps.as_mut(), env.clone(), message_info(&minter, &[]), mint(1)).unwrap_err();
+        assert!(matches!(err, ContractError::MintLimitExceeded { .. }));
     }
 
     #[test]
-    fn burn_works() {
+    fn update_minter_rotates_and_disables() {
         let mut deps = mock_dependencies();
+        let creator = deps.api.addr_make("creator");
+        let minter = deps.api.addr_make("minter");
+        let new_minter = deps.api.addr_make("new_minter");
 
-        // Instantiate the contract
-        let msg = InstantiateMsg {
-            name: "$SEINTS".to_string(),
-            symbol: "SEINTS".to_string(),
-            decimals: 6,
-            initial_supply: Uint128::new(1_000_000_000),
-            metadata_url: "https://bafybeie6fkezbdf3pkioodnvuhjjhjrllcvxovhtam2z7d3qhnur4n4oy4.ipfs.w3s.link/logo.webp".to_string(),
-            team_address: "team".to_string(),
-            pool_address: "pool".to_string(),
+        let mut msg = default_instantiate_msg(&deps.api);
+        msg.mint = Some(MinterInfo {
+            minter: minter.to_string(),
+            cap: None,
+            schedule: None,
+        });
+        instantiate(deps.as_mut(), mock_env(), message_info(&creator, &[]), msg).unwrap();
+
+        // Only the current minter can rotate it, not even a minter role holder
+        let msg = ExecuteMsg::UpdateMinter {
+            new_minter: Some(new_minter.to_string()),
         };
+        let err = execute(deps.as_mut(), mock_env(), message_info(&creator, &[]), msg.clone()).unwrap_err();
+        assert!(matches!(err, ContractError::Unauthorized {}));
+        let res = execute(deps.as_mut(), mock_env(), message_info(&minter, &[]), msg.clone()).unwrap();
+        let event = events::MinterUpdated { new_minter: new_minter.to_string() };
+        assert_eq!(vec![Event::from(event)], res.events);
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::Minter {}).unwrap();
+        let minter_info: Option<MinterResponse> = from_json(&res).unwrap();
+        assert_eq!(
+            Some(MinterResponse {
+                minter: new_minter.to_string(),
+                cap: None,
+                mintable: None,
+            }),
+            minter_info
+        );
 
-        // Use MessageInfo instead of mock_info
-        let info = MessageInfo {
-            sender: Addr::unchecked("creator"),
-            funds: coins(1000, "earth"),
+        // The previous minter lost control
+        let err = execute(deps.as_mut(), mock_env(), message_info(&minter, &[]), msg).unwrap_err();
+        assert!(matches!(err, ContractError::Unauthorized {}));
+
+        // Disabling minting is permanent
+        let msg = ExecuteMsg::UpdateMinter { new_minter: None };
+        let res = execute(deps.as_mut(), mock_env(), message_info(&new_minter, &[]), msg).unwrap();
+        let event = events::MinterUpdated { new_minter: "none".to_string() };
+        assert_eq!(vec![Event::from(event)], res.events);
+        let msg = ExecuteMsg::Mint {
+            recipient: new_minter.to_string(),
+            amount: Uint128::new(1),
+        };
+        let err = execute(deps.as_mut(), mock_env(), message_info(&new_minter, &[]), msg).unwrap_err();
+        assert!(matches!(err, ContractError::MintingDisabled {}));
+        let msg = ExecuteMsg::UpdateMinter {
+            new_minter: Some(minter.to_string()),
         };
+        let err = execute(deps.as_mut(), mock_env(), message_info(&new_minter, &[]), msg).unwrap_err();
+        assert!(matches!(err, ContractError::MintingDisabled {}));
+    }
 
-        instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+    #[test]
+    fn pause_switches_block_only_their_operations() {
+        let mut deps = mock_dependencies();
+        setup_contract(&mut deps);
+        let creator = deps.api.addr_make("creator");
+        let team = deps.api.addr_make("team");
+        let pool = deps.api.addr_make("pool");
 
-        // Burn tokens
-        let burn_msg = ExecuteMsg::Burn {
-            amount: Uint128::new(100),
+        let msg = ExecuteMsg::Pause {
+            operations: Some(vec![Operation::Transfers, Operation::PoolReleases]),
         };
+        let err = execute(deps.as_mut(), mock_env(), message_info(&team, &[]), msg.clone()).unwrap_err();
+        assert!(matches!(err, ContractError::MissingRole { .. }));
+        execute(deps.as_mut(), mock_env(), message_info(&creator, &[]), msg).unwrap();
 
-        // Use MessageInfo instead of mock_info
-        let info = MessageInfo {
-            sender: Addr::unchecked("creator"),
-            funds: vec![],
+        let transfer = ExecuteMsg::Transfer {
+            recipient: pool.to_string(),
+            amount: Uint128::new(1),
         };
+        let err = execute(deps.as_mut(), mock_env(), message_info(&team, &[]), transfer.clone()).unwrap_err();
+        assert!(matches!(err, ContractError::Paused { operation } if operation == "transfers"));
 
-        let res = execute(deps.as_mut(), mock_env(), info, burn_msg).unwrap();
-        assert_eq!(res.attributes.len(), 3);
+        // Keepers cannot push paused pool releases either
+        let mut env = mock_env();
+        env.block.time = env.block.time.plus_seconds(6 * MONTH);
+        let msg = ExecuteMsg::ReleaseFor { beneficiary: pool.to_string() };
+        let err = execute(deps.as_mut(), env, message_info(&team, &[]), msg).unwrap_err();
+        assert!(matches!(err, ContractError::Paused { operation } if operation == "pool_releases"));
 
-        // Verify balances and total supply
-        let creator_balance = BALANCES.load(deps.as_ref().storage, &Addr::unchecked("creator")).unwrap();
-        assert_eq!(Uint128::new(299_999_900), creator_balance); // 300M - 100
+        // Burns were left running
+        let msg = ExecuteMsg::Burn { amount: Uint128::new(1) };
+        execute(deps.as_mut(), mock_env(), message_info(&team, &[]), msg).unwrap();
 
-        let token_info = TOKEN_INFO.load(deps.as_ref().storage).unwrap();
-        assert_eq!(Uint128::new(999_999_900), token_info.total_supply); // 1B - 100
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::PauseStatus {}).unwrap();
+        let status: PauseStatusResponse = from_json(&res).unwrap();
+        assert!(status.transfers && status.pool_releases);
+        assert!(!status.burns && !status.vesting_releases && !status.minting);
+
+        // Unpausing everything restores transfers
+        let msg = ExecuteMsg::Unpause { operations: None };
+        execute(deps.as_mut(), mock_env(), message_info(&creator, &[]), msg).unwrap();
+        execute(deps.as_mut(), mock_env(), message_info(&team, &[]), transfer).unwrap();
     }
 
     #[test]
-    fn release_vested_works() {
+    fn frozen_accounts_cannot_move_tokens() {
         let mut deps = mock_dependencies();
+        setup_contract(&mut deps);
+        let creator = deps.api.addr_make("creator");
+        let team = deps.api.addr_make("team");
+        let thief = deps.api.addr_make("thief");
 
-        // Instantiate the contract
-        let msg = InstantiateMsg {
-            name: "$SEINTS".to_string(),
-            symbol: "SEINTS".to_string(),
-            decimals: 6,
-            initial_supply: Uint128::new(1_000_000_000),
-            metadata_url: "https://bafybeie6fkezbdf3pkioodnvuhjjhjrllcvxovhtam2z7d3qhnur4n4oy4.ipfs.w3s.link/logo.webp".to_string(),
-            team_address: "team".to_string(),
-            pool_address: "pool".to_string(),
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&team, &[]),
+            ExecuteMsg::Transfer {
+                recipient: thief.to_string(),
+                amount: Uint128::new(1_000),
+            },
+        )
+        .unwrap();
+
+        let msg = ExecuteMsg::FreezeAccount {
+            address: thief.to_string(),
+            reason: "stolen funds".to_string(),
         };
+        let err = execute(deps.as_mut(), mock_env(), message_info(&team, &[]), msg.clone()).unwrap_err();
+        assert!(matches!(err, ContractError::MissingRole { .. }));
+        execute(deps.as_mut(), mock_env(), message_info(&creator, &[]), msg).unwrap();
 
-        // Use MessageInfo instead of mock_info
-        let info = MessageInfo {
-            sender: Addr::unchecked("creator"),
-            funds: coins(1000, "earth"),
+        // The frozen account can neither send, burn nor receive
+        let msg = ExecuteMsg::Transfer {
+            recipient: team.to_string(),
+            amount: Uint128::new(1),
+        };
+        let err = execute(deps.as_mut(), mock_env(), message_info(&thief, &[]), msg).unwrap_err();
+        assert!(matches!(err, ContractError::AccountFrozen { .. }));
+        let msg = ExecuteMsg::Burn { amount: Uint128::new(1) };
+        let err = execute(deps.as_mut(), mock_env(), message_info(&thief, &[]), msg).unwrap_err();
+        assert!(matches!(err, ContractError::AccountFrozen { .. }));
+        let msg = ExecuteMsg::Transfer {
+            recipient: thief.to_string(),
+            amount: Uint128::new(1),
         };
+        let err = execute(deps.as_mut(), mock_env(), message_info(&team, &[]), msg.clone()).unwrap_err();
+        assert!(matches!(err, ContractError::AccountFrozen { .. }));
 
-        instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+        let res = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::FrozenAccounts {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+        let frozen: FrozenAccountsResponse = from_json(&res).unwrap();
+        assert_eq!(
+            vec![FrozenAccount {
+                address: thief.to_string(),
+                reason: "stolen funds".to_string(),
+            }],
+            frozen.accounts
+        );
+
+        // Unfreezing restores the account
+        let unfreeze = ExecuteMsg::UnfreezeAccount { address: thief.to_string() };
+        execute(deps.as_mut(), mock_env(), message_info(&creator, &[]), unfreeze).unwrap();
+        execute(deps.as_mut(), mock_env(), message_info(&team, &[]), msg).unwrap();
+    }
+
+    #[test]
+    fn frozen_beneficiary_cannot_release() {
+        let mut deps = mock_dependencies();
+        setup_contract(&mut deps);
+        let creator = deps.api.addr_make("creator");
+
+        let msg = ExecuteMsg::FreezeAccount {
+            address: creator.to_string(),
+            reason: "compromised key".to_string(),
+        };
+        execute(deps.as_mut(), mock_env(), message_info(&creator, &[]), msg).unwrap();
 
-        // Advance time to the first vesting release
         let mut env = mock_env();
-        env.block.time = env.block.time.plus_years(1);
+        env.block.time = env.block.time.plus_seconds(YEAR);
+        let err = execute(deps.as_mut(), env.clone(), message_info(&creator, &[]), ExecuteMsg::ReleaseVested {})
+            .unwrap_err();
+        assert!(matches!(err, ContractError::AccountFrozen { .. }));
+        let keeper = deps.api.addr_make("keeper");
+        let msg = ExecuteMsg::ReleaseFor { beneficiary: creator.to_string() };
+        let err = execute(deps.as_mut(), env, message_info(&keeper, &[]), msg).unwrap_err();
+        assert!(matches!(err, ContractError::AccountFrozen { .. }));
+    }
 
-        // Release vested tokens
-        let release_msg = ExecuteMsg::ReleaseVested {};
-        let info = MessageInfo {
-            sender: Addr::unchecked("creator"),
-            funds: vec![],
+    #[test]
+    fn transfer_fee_is_split_and_skips_exempt_accounts() {
+        let mut deps = mock_dependencies();
+        setup_contract(&mut deps);
+        let creator = deps.api.addr_make("creator");
+        let team = deps.api.addr_make("team");
+        let pool = deps.api.addr_make("pool");
+        let treasury = deps.api.addr_make("treasury");
+        let dex = deps.api.addr_make("dex");
+
+        // A 1% fee: half to the treasury, 30% burned and 20% to the pool
+        let msg = ExecuteMsg::UpdateFeeConfig {
+            rate_bps: 100,
+            treasury_share_bps: 5_000,
+            burn_share_bps: 3_000,
+            pool_share_bps: 2_000,
         };
+        let err = execute(deps.as_mut(), mock_env(), message_info(&team, &[]), msg.clone()).unwrap_err();
+        assert!(matches!(err, ContractError::MissingRole { .. }));
+        execute(deps.as_mut(), mock_env(), message_info(&creator, &[]), msg).unwrap();
 
-        let res = execute(deps.as_mut(), env.clone(), info, release_msg).unwrap();
-        assert_eq!(res.attributes.len(), 2);
+        let msg = ExecuteMsg::UpdateFeeConfig {
+            rate_bps: MAX_FEE_BPS + 1,
+            treasury_share_bps: 10_000,
+            burn_share_bps: 0,
+            pool_share_bps: 0,
+        };
+        let err = execute(deps.as_mut(), mock_env(), message_info(&creator, &[]), msg).unwrap_err();
+        assert!(matches!(err, ContractError::InvalidFeeConfig {}));
 
-        // Verify balances
-        let creator_balance = BALANCES.load(deps.as_ref().storage, &Addr::unchecked("creator")).unwrap();
-        assert_eq!(Uint128::new(300_000_000), creator_balance); // 300M (initial) + 10% of 300M
+        // Fee shares still reach the pool while it is frozen
+        let msg = ExecuteMsg::FreezeAccount {
+            address: pool.to_string(),
+            reason: "audit".to_string(),
+        };
+        execute(deps.as_mut(), mock_env(), message_info(&creator, &[]), msg).unwrap();
+
+        let msg = ExecuteMsg::Transfer {
+            recipient: dex.to_string(),
+            amount: Uint128::new(10_000),
+        };
+        let res = execute(deps.as_mut(), mock_env(), message_info(&team, &[]), msg).unwrap();
+        let event = events::Transfer {
+            from: team.clone(),
+            to: dex.clone(),
+            amount: Uint128::new(10_000),
+            fee: Uint128::new(100),
+            fee_treasury: Uint128::new(50),
+            fee_burned: Uint128::new(30),
+            fee_pool: Uint128::new(20),
+        };
+        assert_eq!(vec![Event::from(event)], res.events);
 
-        // Verify vesting schedule
-        let vesting_info = VESTING.load(deps.as_ref().storage, &Addr::unchecked("creator")).unwrap();
-        assert_eq!(vesting_info.release_schedule.len(), 2); // 2 releases remaining
+        assert_eq!(Uint128::new(9_900), BALANCES.load(deps.as_ref().storage, &dex).unwrap());
+        assert_eq!(Uint128::new(50), BALANCES.load(deps.as_ref().storage, &treasury).unwrap());
+        assert_eq!(Uint128::new(400_000_020), BALANCES.load(deps.as_ref().storage, &pool).unwrap());
+        let token_info = TOKEN_INFO.load(deps.as_ref().storage).unwrap();
+        assert_eq!(Uint128::new(999_999_970), token_info.total_supply);
+
+        // Trades with an exempt DEX pair are free
+        let msg = ExecuteMsg::SetFeeExempt {
+            address: dex.to_string(),
+            exempt: true,
+        };
+        execute(deps.as_mut(), mock_env(), message_info(&creator, &[]), msg).unwrap();
+        let msg = ExecuteMsg::Transfer {
+            recipient: team.to_string(),
+            amount: Uint128::new(9_900),
+        };
+        let res = execute(deps.as_mut(), mock_env(), message_info(&dex, &[]), msg).unwrap();
+        assert_eq!(Some(&attr("fee", "0")), res.events[0].attributes.iter().find(|a| a.key == "fee"));
+        assert_eq!(Uint128::zero(), BALANCES.load(deps.as_ref().storage, &dex).unwrap());
     }
 
     #[test]
-    fn release_pool_works() {
+    fn balance_and_supply_at_height() {
         let mut deps = mock_dependencies();
+        setup_contract(&mut deps);
+        let team = deps.api.addr_make("team");
+        let alice = deps.api.addr_make("alice");
+        let start = mock_env().block.height;
 
-        // Instantiate the contract
-        let msg = InstantiateMsg {
-            name: "$SEINTS".to_string(),
-            symbol: "SEINTS".to_string(),
-            decimals: 6,
-            initial_supply: Uint128::new(1_000_000_000),
-            metadata_url: "https://bafybeie6fkezbdf3pkioodnvuhjjhjrllcvxovhtam2z7d3qhnur4n4oy4.ipfs.w3s.link/logo.webp".to_string(),
-            team_address: "team".to_string(),
-            pool_address: "pool".to_string(),
+        let mut env = mock_env();
+        env.block.height = start + 10;
+        let msg = ExecuteMsg::Transfer {
+            recipient: alice.to_string(),
+            amount: Uint128::new(1_000),
         };
+        execute(deps.as_mut(), env.clone(), message_info(&team, &[]), msg).unwrap();
+        env.block.height = start + 20;
+        let msg = ExecuteMsg::Burn { amount: Uint128::new(400) };
+        execute(deps.as_mut(), env, message_info(&alice, &[]), msg).unwrap();
 
-        // Use MessageInfo instead of mock_info
-        let info = MessageInfo {
-            sender: Addr::unchecked("creator"),
-            funds: coins(1000, "earth"),
+        let balance_at = |deps: &OwnedDeps<MockStorage, MockApi, MockQuerier>, height| {
+            let msg = QueryMsg::BalanceAtHeight {
+                address: alice.to_string(),
+                height,
+            };
+            from_json::<BalanceResponse>(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap().balance
+        };
+        let supply_at = |deps: &OwnedDeps<MockStorage, MockApi, MockQuerier>, height| {
+            let msg = QueryMsg::TotalSupplyAtHeight { height };
+            from_json::<TotalSupplyResponse>(&query(deps.as_ref(), mock_env(), msg).unwrap())
+                .unwrap()
+                .total_supply
         };
 
-        instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+        // A change at some height is visible from the next block on
+        assert_eq!(Uint128::zero(), balance_at(&deps, Some(start + 10)));
+        assert_eq!(Uint128::new(1_000), balance_at(&deps, Some(start + 11)));
+        assert_eq!(Uint128::new(1_000), balance_at(&deps, Some(start + 20)));
+        assert_eq!(Uint128::new(600), balance_at(&deps, Some(start + 21)));
+        assert_eq!(Uint128::new(600), balance_at(&deps, None));
 
-        // Advance time to the first pool release
+        assert_eq!(Uint128::new(1_000_000_000), supply_at(&deps, Some(start + 20)));
+        assert_eq!(Uint128::new(999_999_600), supply_at(&deps, Some(start + 21)));
+        assert_eq!(Uint128::new(999_999_600), supply_at(&deps, None));
+    }
+
+    #[test]
+    fn delegated_votes_follow_balances() {
+        let mut deps = mock_dependencies();
+        setup_contract(&mut deps);
+        let team = deps.api.addr_make("team");
+        let alice = deps.api.addr_make("alice");
+        let dao = deps.api.addr_make("dao");
+        let start = mock_env().block.height;
+
+        let voting_power = |deps: &OwnedDeps<MockStorage, MockApi, MockQuerier>, address: &Addr, height| {
+            let msg = QueryMsg::VotingPower {
+                address: address.to_string(),
+                height,
+            };
+            from_json::<VotingPowerResponse>(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap().power
+        };
+
+        // Holders have no votes until they delegate, even to themselves
+        assert_eq!(Uint128::zero(), voting_power(&deps, &team, None));
+        let msg = ExecuteMsg::Delegate { delegatee: dao.to_string() };
+        let res = execute(deps.as_mut(), mock_env(), message_info(&team, &[]), msg).unwrap();
+        assert_eq!(Uint128::new(200_000_000), voting_power(&deps, &dao, None));
+        let event = events::Delegated {
+            delegator: team.clone(),
+            delegatee: dao.clone(),
+            weight: Uint128::new(200_000_000),
+        };
+        assert_eq!(vec![Event::from(event)], res.events);
+
+        // A transfer moves weight away from the delegatee, a burn removes it
         let mut env = mock_env();
-        env.block.time = env.block.time.plus_months(6);
+        env.block.height = start + 5;
+        let msg = ExecuteMsg::Transfer {
+            recipient: alice.to_string(),
+            amount: Uint128::new(50_000_000),
+        };
+        execute(deps.as_mut(), env.clone(), message_info(&team, &[]), msg).unwrap();
+        let msg = ExecuteMsg::Burn { amount: Uint128::new(10_000_000) };
+        execute(deps.as_mut(), env.clone(), message_info(&team, &[]), msg).unwrap();
+        assert_eq!(Uint128::new(140_000_000), voting_power(&deps, &dao, None));
+        assert_eq!(Uint128::new(200_000_000), voting_power(&deps, &dao, Some(start + 5)));
 
-        // Release pool tokens
-        let release_msg = ExecuteMsg::ReleasePool {};
-        let info = MessageInfo {
-            sender: Addr::unchecked("pool"),
-            funds: vec![],
+        // Redelegating moves the whole remaining weight
+        let msg = ExecuteMsg::Delegate { delegatee: team.to_string() };
+        execute(deps.as_mut(), env, message_info(&team, &[]), msg).unwrap();
+        assert_eq!(Uint128::zero(), voting_power(&deps, &dao, None));
+        assert_eq!(Uint128::new(140_000_000), voting_power(&deps, &team, None));
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::Delegates { address: team.to_string() }).unwrap();
+        let delegates: DelegatesResponse = from_json(&res).unwrap();
+        assert_eq!(Some(team.to_string()), delegates.delegate);
+    }
+
+    #[test]
+    fn locked_vesting_counts_toward_votes_when_enabled() {
+        let mut deps = mock_dependencies();
+        let creator = deps.api.addr_make("creator");
+        let mut msg = default_instantiate_msg(&deps.api);
+        msg.vesting_votes = true;
+        instantiate(deps.as_mut(), mock_env(), message_info(&creator, &[]), msg).unwrap();
+
+        // The creator holds no balance but 300M locked in vesting
+        let msg = ExecuteMsg::Delegate { delegatee: creator.to_string() };
+        execute(deps.as_mut(), mock_env(), message_info(&creator, &[]), msg).unwrap();
+        assert_eq!(Uint128::new(300_000_000), VOTES.load(deps.as_ref().storage, &creator).unwrap());
+
+        // Releasing moves tokens from locked to the balance without changing the weight
+        let mut env = mock_env();
+        env.block.time = env.block.time.plus_seconds(YEAR);
+        execute(deps.as_mut(), env, message_info(&creator, &[]), ExecuteMsg::ReleaseVested {}).unwrap();
+        assert_eq!(Uint128::new(300_000_000), VOTES.load(deps.as_ref().storage, &creator).unwrap());
+        assert_eq!(Uint128::new(100_000_000), BALANCES.load(deps.as_ref().storage, &creator).unwrap());
+    }
+
+    #[test]
+    fn staking_rewards_and_unbonding() {
+        let mut deps = mock_dependencies();
+        let creator = deps.api.addr_make("creator");
+        let mut msg = default_instantiate_msg(&deps.api);
+        msg.unbonding_period = 100;
+        instantiate(deps.as_mut(), mock_env(), message_info(&creator, &[]), msg).unwrap();
+        let team = deps.api.addr_make("team");
+        let pool = deps.api.addr_make("pool");
+
+        let msg = ExecuteMsg::Stake { amount: Uint128::new(100_000_000) };
+        let res = execute(deps.as_mut(), mock_env(), message_info(&team, &[]), msg).unwrap();
+        let event = events::Staked {
+            staker: team.clone(),
+            amount: Uint128::new(100_000_000),
+            staked: Uint128::new(100_000_000),
         };
+        assert_eq!(vec![Event::from(event)], res.events);
 
-        let res = execute(deps.as_mut(), env.clone(), info, release_msg).unwrap();
-        assert_eq!(res.attributes.len(), 2);
+        // Only the pool, or the owner on its behalf, can fund rewards
+        let msg = ExecuteMsg::FundRewards {
+            amount: Uint128::new(1_000),
+            duration: 100,
+        };
+        let err = execute(deps.as_mut(), mock_env(), message_info(&team, &[]), msg.clone()).unwrap_err();
+        assert!(matches!(err, ContractError::Unauthorized {}));
+        let res = execute(deps.as_mut(), mock_env(), message_info(&pool, &[]), msg).unwrap();
+        let event = events::RewardsFunded {
+            funder: pool.clone(),
+            amount: Uint128::new(1_000),
+            reward_rate: Uint128::new(10),
+            period_finish: mock_env().block.time.seconds() + 100,
+        };
+        assert_eq!(vec![Event::from(event)], res.events);
 
-        // Verify balances
-        let pool_balance = BALANCES.load(deps.as_ref().storage, &Addr::unchecked("pool")).unwrap();
-        assert_eq!(Uint128::new(450_000_000), pool_balance); // 400M (initial) + 50M (10% of 500M)
+        // Half the period has passed, so half the rewards are due
+        let mut env = mock_env();
+        env.block.time = env.block.time.plus_seconds(50);
+        let res = query(deps.as_ref(), env.clone(), QueryMsg::PendingRewards { address: team.to_string() }).unwrap();
+        let pending: PendingRewardsResponse = from_json(&res).unwrap();
+        assert_eq!(Uint128::new(500), pending.rewards);
+        let res = execute(deps.as_mut(), env.clone(), message_info(&team, &[]), ExecuteMsg::ClaimRewards {}).unwrap();
+        assert_eq!(Uint128::new(100_000_500), BALANCES.load(deps.as_ref().storage, &team).unwrap());
+        let event = events::RewardsClaimed {
+            staker: team.clone(),
+            amount: Uint128::new(500),
+        };
+        assert_eq!(vec![Event::from(event)], res.events);
+
+        // Unstaked tokens can only be claimed once the unbonding period has passed
+        let msg = ExecuteMsg::Unstake { amount: Uint128::new(100_000_000) };
+        let res = execute(deps.as_mut(), env.clone(), message_info(&team, &[]), msg).unwrap();
+        let event = events::Unstaked {
+            staker: team.clone(),
+            amount: Uint128::new(100_000_000),
+            release_at: env.block.time.seconds() + 100,
+        };
+        assert_eq!(vec![Event::from(event)], res.events);
+        let err = execute(deps.as_mut(), env.clone(), message_info(&team, &[]), ExecuteMsg::ClaimUnbonded {}).unwrap_err();
+        assert!(matches!(err, ContractError::NothingToClaim {}));
+        let res = query(deps.as_ref(), env.clone(), QueryMsg::UnbondingEntries { address: team.to_string() }).unwrap();
+        let unbonding: UnbondingEntriesResponse = from_json(&res).unwrap();
+        assert_eq!(env.block.time.plus_seconds(100), unbonding.entries[0].release_at);
+
+        env.block.time = env.block.time.plus_seconds(100);
+        let res = execute(deps.as_mut(), env.clone(), message_info(&team, &[]), ExecuteMsg::ClaimUnbonded {}).unwrap();
+        assert_eq!(Uint128::new(200_000_500), BALANCES.load(deps.as_ref().storage, &team).unwrap());
+        let event = events::UnbondedClaimed {
+            staker: team.clone(),
+            amount: Uint128::new(100_000_000),
+        };
+        assert_eq!(vec![Event::from(event)], res.events);
+
+        // Nothing was staked for the rest of the period, so nothing more accrued
+        let res = query(deps.as_ref(), env.clone(), QueryMsg::Staked { address: team.to_string() }).unwrap();
+        let staked: StakedResponse = from_json(&res).unwrap();
+        assert_eq!(Uint128::zero(), staked.total_staked);
 
-        // Verify pool release schedule
-        let pool_release_info = POOL_RELEASE_SCHEDULE.load(deps.as_ref().storage, &Addr::unchecked("pool")).unwrap();
-        assert_eq!(pool_release_info.release_schedule.len(), 2); // 2 releases remaining
+        // The rewards of the idle time are kept for the next staker
+        let msg = ExecuteMsg::Stake { amount: Uint128::new(100_000_000) };
+        execute(deps.as_mut(), env.clone(), message_info(&team, &[]), msg).unwrap();
+        env.block.time = env.block.time.plus_seconds(1_000);
+        let res = query(deps.as_ref(), env.clone(), QueryMsg::PendingRewards { address: team.to_string() }).unwrap();
+        let pending: PendingRewardsResponse = from_json(&res).unwrap();
+        assert_eq!(Uint128::new(500), pending.rewards);
+
+        // Staking moves are paused with transfers
+        let msg = ExecuteMsg::Pause { operations: Some(vec![Operation::Transfers]) };
+        execute(deps.as_mut(), env.clone(), message_info(&creator, &[]), msg).unwrap();
+        let msg = ExecuteMsg::Unstake { amount: Uint128::new(100_000_000) };
+        let err = execute(deps.as_mut(), env.clone(), message_info(&team, &[]), msg).unwrap_err();
+        assert!(matches!(err, ContractError::Paused { .. }));
+        let err = execute(deps.as_mut(), env, message_info(&team, &[]), ExecuteMsg::ClaimRewards {}).unwrap_err();
+        assert!(matches!(err, ContractError::Paused { .. }));
     }
 
     #[test]
-    fn update_metadata_works() {
+    fn merkle_airdrop_claims_and_reclaim() {
         let mut deps = mock_dependencies();
+        setup_contract(&mut deps);
+        let creator = deps.api.addr_make("creator");
+        let pool = deps.api.addr_make("pool");
+        let alice = deps.api.addr_make("alice");
+        let bob = deps.api.addr_make("bob");
 
-        // Instantiate the contract
-        let msg = InstantiateMsg {
-            name: "$SEINTS".to_string(),
-            symbol: "SEINTS".to_string(),
-            decimals: 6,
-            initial_supply: Uint128::new(1_000_000_000),
-            metadata_url: "https://bafybeie6fkezbdf3pkioodnvuhjjhjrllcvxovhtam2z7d3qhnur4n4oy4.ipfs.w3s.link/logo.webp".to_string(),
-            team_address: "team".to_string(),
-            pool_address: "pool".to_string(),
+        let claims = [(alice.clone(), Uint128::new(1_000)), (bob.clone(), Uint128::new(2_000))];
+        let leaves: Vec<_> = claims.iter().map(|(address, amount)| airdrop::leaf(address.as_str(), *amount)).collect();
+        let (root, proofs) = airdrop::build(&leaves);
+        let proof = |i: usize| proofs[i].iter().map(hex::encode).collect::<Vec<_>>();
+        let expiration = Expiration::AtHeight(mock_env().block.height + 100);
+
+        let msg = ExecuteMsg::RegisterMerkleRoot {
+            stage: 1,
+            root: hex::encode(root),
+            expiration,
+            total_amount: Uint128::new(5_000),
+        };
+        let err = execute(deps.as_mut(), mock_env(), message_info(&alice, &[]), msg.clone()).unwrap_err();
+        assert!(matches!(err, ContractError::Unauthorized {}));
+        // The owner registers the stage on the pool's behalf, from the pool's balance
+        let res = execute(deps.as_mut(), mock_env(), message_info(&creator, &[]), msg).unwrap();
+        assert_eq!(Uint128::new(399_995_000), BALANCES.load(deps.as_ref().storage, &pool).unwrap());
+        let event = events::AirdropRegistered {
+            stage: 1,
+            root: hex::encode(root),
+            total_amount: Uint128::new(5_000),
         };
+        assert_eq!(vec![Event::from(event)], res.events);
 
-        // Use MessageInfo instead of mock_info
-        let info = MessageInfo {
-            sender: Addr::unchecked("creator"),
-            funds: coins(1000, "earth"),
+        // A wrong amount fails the proof, the right one pays out once
+        let msg = ExecuteMsg::ClaimAirdrop { stage: 1, amount: Uint128::new(2_000), proof: proof(0) };
+        let err = execute(deps.as_mut(), mock_env(), message_info(&alice, &[]), msg).unwrap_err();
+        assert!(matches!(err, ContractError::InvalidProof {}));
+        let msg = ExecuteMsg::ClaimAirdrop { stage: 1, amount: Uint128::new(1_000), proof: proof(0) };
+        let res = execute(deps.as_mut(), mock_env(), message_info(&alice, &[]), msg.clone()).unwrap();
+        let event = events::AirdropClaimed {
+            stage: 1,
+            address: alice.clone(),
+            amount: Uint128::new(1_000),
         };
+        assert_eq!(vec![Event::from(event)], res.events);
+        let err = execute(deps.as_mut(), mock_env(), message_info(&alice, &[]), msg).unwrap_err();
+        assert!(matches!(err, ContractError::AlreadyClaimed { stage: 1, .. }));
+        assert_eq!(Uint128::new(1_000), BALANCES.load(deps.as_ref().storage, &alice).unwrap());
 
-        instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+        // After expiry Bob can no longer claim and the owner returns the rest to the pool
+        let mut env = mock_env();
+        env.block.height += 100;
+        let msg = ExecuteMsg::ClaimAirdrop { stage: 1, amount: Uint128::new(2_000), proof: proof(1) };
+        let err = execute(deps.as_mut(), env.clone(), message_info(&bob, &[]), msg).unwrap_err();
+        assert!(matches!(err, ContractError::StageExpired { stage: 1 }));
+        let err = execute(deps.as_mut(), mock_env(), message_info(&creator, &[]), ExecuteMsg::ReclaimAirdrop { stage: 1 })
+            .unwrap_err();
+        assert!(matches!(err, ContractError::StageNotExpired { stage: 1 }));
+        let res =
+            execute(deps.as_mut(), env, message_info(&creator, &[]), ExecuteMsg::ReclaimAirdrop { stage: 1 }).unwrap();
+        assert_eq!(Uint128::new(399_999_000), BALANCES.load(deps.as_ref().storage, &pool).unwrap());
+        let event = events::AirdropReclaimed {
+            stage: 1,
+            recipient: pool.clone(),
+            amount: Uint128::new(4_000),
+        };
+        assert_eq!(vec![Event::from(event)], res.events);
+    }
 
-        // Update metadata
-        let update_msg = ExecuteMsg::UpdateMetadata {
-            metadata_url: "https://new-metadata-url.ipfs.w3s.link/logo.webp".to_string(),
+    #[test]
+    fn batch_transfer() {
+        let mut deps = mock_dependencies();
+        setup_contract(&mut deps);
+        let creator = deps.api.addr_make("creator");
+        let team = deps.api.addr_make("team");
+        let alice = deps.api.addr_make("alice");
+        let bob = deps.api.addr_make("bob");
+
+        let msg = ExecuteMsg::BatchTransfer {
+            transfers: vec![(alice.to_string(), Uint128::new(100)), (bob.to_string(), Uint128::new(250))],
         };
+        let res = execute(deps.as_mut(), mock_env(), message_info(&team, &[]), msg).unwrap();
+        assert_eq!(Uint128::new(100), BALANCES.load(deps.as_ref().storage, &alice).unwrap());
+        assert_eq!(Uint128::new(250), BALANCES.load(deps.as_ref().storage, &bob).unwrap());
+        assert_eq!(Uint128::new(199_999_650), BALANCES.load(deps.as_ref().storage, &team).unwrap());
+        let batch = events::BatchTransfer {
+            from: team.clone(),
+            recipients: 2,
+            total: Uint128::new(350),
+            fee: Uint128::zero(),
+            fee_treasury: Uint128::zero(),
+            fee_burned: Uint128::zero(),
+            fee_pool: Uint128::zero(),
+        };
+        let transfer = |to: &Addr, amount| events::Transfer {
+            from: team.clone(),
+            to: to.clone(),
+            amount: Uint128::new(amount),
+            fee: Uint128::zero(),
+            fee_treasury: Uint128::zero(),
+            fee_burned: Uint128::zero(),
+            fee_pool: Uint128::zero(),
+        };
+        let expected = vec![Event::from(batch), transfer(&alice, 100).into(), transfer(&bob, 250).into()];
+        assert_eq!(expected, res.events);
 
-        // Use MessageInfo instead of mock_info
-        let info = MessageInfo {
-            sender: Addr::unchecked("creator"),
-            funds: vec![],
+        // Duplicates and zero amounts reject the whole batch
+        let msg = ExecuteMsg::BatchTransfer {
+            transfers: vec![(alice.to_string(), Uint128::new(1)), (alice.to_string(), Uint128::new(1))],
         };
+        let err = execute(deps.as_mut(), mock_env(), message_info(&team, &[]), msg).unwrap_err();
+        assert!(matches!(err, ContractError::DuplicateAddresses { .. }));
+        let msg = ExecuteMsg::BatchTransfer {
+            transfers: vec![(alice.to_string(), Uint128::new(1)), (bob.to_string(), Uint128::zero())],
+        };
+        let err = execute(deps.as_mut(), mock_env(), message_info(&team, &[]), msg).unwrap_err();
+        assert!(matches!(err, ContractError::InvalidAmount { .. }));
+        assert_eq!(Uint128::new(100), BALANCES.load(deps.as_ref().storage, &alice).unwrap());
 
-        let res = execute(deps.as_mut(), mock_env(), info, update_msg).unwrap();
-        assert_eq!(res.attributes.len(), 2);
+        // The owner can lower the batch size limit, but not to zero or above the hard cap
+        for max_batch_size in [0, MAX_BATCH_SIZE_LIMIT + 1] {
+            let msg = ExecuteMsg::UpdateMaxBatchSize { max_batch_size };
+            let err = execute(deps.as_mut(), mock_env(), message_info(&creator, &[]), msg).unwrap_err();
+            assert!(matches!(err, ContractError::InvalidMaxBatchSize { .. }));
+        }
+        let msg = ExecuteMsg::UpdateMaxBatchSize { max_batch_size: 1 };
+        let res = execute(deps.as_mut(), mock_env(), message_info(&creator, &[]), msg).unwrap();
+        assert_eq!(vec![Event::from(events::MaxBatchSizeUpdated { max_batch_size: 1 })], res.events);
+        let msg = ExecuteMsg::BatchTransfer {
+            transfers: vec![(alice.to_string(), Uint128::new(1)), (bob.to_string(), Uint128::new(1))],
+        };
+        let err = execute(deps.as_mut(), mock_env(), message_info(&team, &[]), msg).unwrap_err();
+        assert!(matches!(err, ContractError::BatchTooLarge { size: 2, max: 1 }));
+    }
 
-        // Verify metadata URL
-        let res = query(deps.as_ref(), mock_env(), QueryMsg::GetMetadata {}).unwrap();
-        let metadata: MetadataResponse = from_binary(&res).unwrap();
-        assert_eq!("https://new-metadata-url.ipfs.w3s.link/logo.webp", metadata.metadata_url);
+    #[test]
+    fn permit_transfer_by_relayer() {
+        use crate::msg::PermitParams;
+        use k256::ecdsa::{signature::hazmat::PrehashSigner, Signature, SigningKey};
+
+        let mut deps = mock_dependencies();
+        setup_contract(&mut deps);
+        let team = deps.api.addr_make("team");
+        let relayer = deps.api.addr_make("relayer");
+        let alice = deps.api.addr_make("alice");
+
+        // A holder whose address derives from its key, funded by the team
+        let key = SigningKey::from_bytes(&[7u8; 32].into()).unwrap();
+        let pubkey = key.verifying_key().to_encoded_point(true).as_bytes().to_vec();
+        let holder = deps.api.addr_humanize(&permit::pubkey_to_canonical(&pubkey)).unwrap();
+        let msg = ExecuteMsg::Transfer {
+            recipient: holder.to_string(),
+            amount: Uint128::new(1_000),
+        };
+        execute(deps.as_mut(), mock_env(), message_info(&team, &[]), msg).unwrap();
+
+        let env = mock_env();
+        let sign = |params: PermitParams| {
+            let signature: Signature = key.sign_prehash(&permit::digest(&params).unwrap()).unwrap();
+            Permit {
+                params,
+                pubkey: pubkey.clone().into(),
+                signature: signature.to_bytes().to_vec().into(),
+            }
+        };
+        let params = PermitParams {
+            owner: holder.to_string(),
+            action: PermitAction::Transfer {
+                recipient: alice.to_string(),
+                amount: Uint128::new(400),
+            },
+            nonce: 0,
+            expires: Expiration::AtHeight(env.block.height + 10),
+            chain_id: env.block.chain_id.clone(),
+            contract: env.contract.address.to_string(),
+        };
+        let permit = sign(params.clone());
+        let msg = ExecuteMsg::Permit { permit: permit.clone() };
+        let res = execute(deps.as_mut(), env.clone(), message_info(&relayer, &[]), msg).unwrap();
+        assert_eq!(Uint128::new(400), BALANCES.load(deps.as_ref().storage, &alice).unwrap());
+        assert_eq!(Uint128::new(600), BALANCES.load(deps.as_ref().storage, &holder).unwrap());
+
+        // The transfer reports its usual event, followed by the permit that authorised it
+        let transfer = events::Transfer {
+            from: holder.clone(),
+            to: alice.clone(),
+            amount: Uint128::new(400),
+            fee: Uint128::zero(),
+            fee_treasury: Uint128::zero(),
+            fee_burned: Uint128::zero(),
+            fee_pool: Uint128::zero(),
+        };
+        let permit_used = events::PermitUsed {
+            owner: holder.clone(),
+            nonce: 0,
+            relayer: relayer.clone(),
+        };
+        assert_eq!(vec![Event::from(transfer), Event::from(permit_used)], res.events);
+
+        // The same permit cannot be replayed
+        let err = execute(deps.as_mut(), env.clone(), message_info(&relayer, &[]), ExecuteMsg::Permit { permit })
+            .unwrap_err();
+        assert!(matches!(err, ContractError::InvalidNonce { expected: 1, actual: 0 }));
+
+        // Tampering with the signed params breaks the signature
+        let mut permit = sign(PermitParams { nonce: 1, ..params });
+        permit.params.action = PermitAction::Transfer {
+            recipient: alice.to_string(),
+            amount: Uint128::new(600),
+        };
+        let err = execute(deps.as_mut(), env, message_info(&relayer, &[]), ExecuteMsg::Permit { permit }).unwrap_err();
+        assert!(matches!(err, ContractError::InvalidSignature {}));
+    }
+
+    #[test]
+    fn convert_to_native_escrows_and_mints() {
+        let mut deps = mock_dependencies();
+        setup_contract(&mut deps);
+        let creator = deps.api.addr_make("creator");
+        let team = deps.api.addr_make("team");
+
+        let msg = ExecuteMsg::ConvertToNative { amount: Uint128::new(1_000) };
+        let err = execute(deps.as_mut(), mock_env(), message_info(&team, &[]), msg.clone()).unwrap_err();
+        assert!(matches!(err, ContractError::NativeDenomNotSet {}));
+
+        let create = ExecuteMsg::CreateNativeDenom { subdenom: "seints".to_string() };
+        let res = execute(deps.as_mut(), mock_env(), message_info(&creator, &[]), create).unwrap();
+        let denom = format!("factory/{}/seints", mock_env().contract.address);
+        assert_eq!(vec![Event::from(events::NativeDenomCreated { denom: denom.clone() })], res.events);
+        let res = execute(deps.as_mut(), mock_env(), message_info(&team, &[]), msg).unwrap();
+        let coins = cosmwasm_std::coins(1_000, &denom);
+        assert_eq!(res.messages[0].msg, CosmosMsg::Custom(SeiMsg::MintTokens { amount: coins[0].clone() }));
+        assert_eq!(res.messages[1].msg, CosmosMsg::Bank(BankMsg::Send { to_address: team.to_string(), amount: coins }));
+        assert_eq!(Uint128::new(199_999_000), BALANCES.load(deps.as_ref().storage, &team).unwrap());
+        let event = events::ConvertedToNative {
+            address: team.clone(),
+            denom: denom.clone(),
+            amount: Uint128::new(1_000),
+        };
+        assert_eq!(vec![Event::from(event)], res.events);
+
+        // Only the wrapping denom converts back
+        let other = cosmwasm_std::coins(1, "usei");
+        let err = execute(deps.as_mut(), mock_env(), message_info(&team, &other), ExecuteMsg::ConvertFromNative {})
+            .unwrap_err();
+        assert!(matches!(err, ContractError::Payment(_)));
+        let funds = cosmwasm_std::coins(400, &denom);
+        let msg = ExecuteMsg::ConvertFromNative {};
+        let res = execute(deps.as_mut(), mock_env(), message_info(&team, &funds), msg).unwrap();
+        assert_eq!(Uint128::new(600), NATIVE_ESCROW.load(deps.as_ref().storage).unwrap());
+        assert_eq!(res.messages[0].msg, CosmosMsg::Custom(SeiMsg::BurnTokens { amount: funds[0].clone() }));
+        let event = events::ConvertedFromNative {
+            address: team,
+            denom,
+            amount: Uint128::new(400),
+        };
+        assert_eq!(vec![Event::from(event)], res.events);
+    }
+
+    #[test]
+    fn convert_to_native_checks_the_supply_invariant() {
+        let mut deps = mock_dependencies();
+        setup_contract(&mut deps);
+        let creator = deps.api.addr_make("creator");
+        let team = deps.api.addr_make("team");
+        let create = ExecuteMsg::CreateNativeDenom { subdenom: "seints".to_string() };
+        execute(deps.as_mut(), mock_env(), message_info(&creator, &[]), create).unwrap();
+
+        // The escrow can never hold more than the 1B total supply
+        NATIVE_ESCROW.save(deps.as_mut().storage, &Uint128::new(999_999_001)).unwrap();
+        let msg = ExecuteMsg::ConvertToNative { amount: Uint128::new(1_000) };
+        let err = execute(deps.as_mut(), mock_env(), message_info(&team, &[]), msg.clone()).unwrap_err();
+        assert!(matches!(
+            err,
+            ContractError::SupplyInvariant { escrowed, total_supply }
+                if escrowed == Uint128::new(1_000_000_001) && total_supply == Uint128::new(1_000_000_000)
+        ));
+        NATIVE_ESCROW.save(deps.as_mut().storage, &Uint128::new(400_000_000)).unwrap();
+        execute(deps.as_mut(), mock_env(), message_info(&team, &[]), msg).unwrap();
+
+        // Burning more than is escrowed is refused too
+        let escrowed = NATIVE_ESCROW.load(deps.as_ref().storage).unwrap();
+        let denom = format!("factory/{}/seints", mock_env().contract.address);
+        let funds = cosmwasm_std::coins(escrowed.u128() + 1, &denom);
+        let err = execute(deps.as_mut(), mock_env(), message_info(&team, &funds), ExecuteMsg::ConvertFromNative {})
+            .unwrap_err();
+        assert!(matches!(err, ContractError::SupplyInvariant { .. }));
+    }
+
+    #[test]
+    fn execute_messages_emit_typed_events() {
+        let mut deps = mock_dependencies();
+        let creator = deps.api.addr_make("creator");
+        let team = deps.api.addr_make("team");
+        let pool = deps.api.addr_make("pool");
+        let alice = deps.api.addr_make("alice");
+        let bob = deps.api.addr_make("bob");
+        let carol = deps.api.addr_make("carol");
+        let keeper = deps.api.addr_make("keeper");
+        let receiver = deps.api.addr_make("receiver");
+
+        let msg = default_instantiate_msg(&deps.api);
+        let res = instantiate(deps.as_mut(), mock_env(), message_info(&creator, &[]), msg).unwrap();
+        let event = events::Instantiated {
+            owner: creator.clone(),
+            total_supply: Uint128::new(1_000_000_000),
+            metadata_url: TEST_METADATA_URL.to_string(),
+        };
+        assert_eq!(vec![Event::from(event)], res.events);
+
+        // Every message emits exactly one event of its own type, with the documented keys
+        let now = mock_env().block.time.seconds();
+        let schedule = Schedule {
+            clock: Clock::Time,
+            curve: Curve::Stepped {
+                steps: vec![(now + YEAR, Uint128::new(1_000))],
+            },
+        };
+        let cases: Vec<(&Addr, ExecuteMsg, Event)> = vec![
+            (
+                &creator,
+                ExecuteMsg::UpdateKeeperTip {
+                    tip: Uint128::new(10),
+                    min_release: Uint128::new(1_000),
+                },
+                events::KeeperTipUpdated {
+                    tip: Uint128::new(10),
+                    min_release: Uint128::new(1_000),
+                }
+                .into(),
+            ),
+            (
+                &team,
+                ExecuteMsg::FundKeeperReserve { amount: Uint128::new(15) },
+                events::KeeperReserveFunded {
+                    from: team.clone(),
+                    amount: Uint128::new(15),
+                    reserve: Uint128::new(15),
+                }
+                .into(),
+            ),
+            (
+                &keeper,
+                ExecuteMsg::ReleaseBatch {
+                    beneficiaries: vec![pool.to_string(), creator.to_string()],
+                },
+                events::BatchReleased {
+                    keeper: keeper.clone(),
+                    beneficiaries: 2,
+                    released: 1,
+                    skipped: 0,
+                    amount: Uint128::new(50_000_000),
+                    tip: Uint128::new(10),
+                }
+                .into(),
+            ),
+            (
+                &creator,
+                ExecuteMsg::GrantRole {
+                    role: Role::VestingAdmin,
+                    address: team.to_string(),
+                },
+                events::RoleGranted {
+                    role: "vesting_admin".to_string(),
+                    address: team.clone(),
+                }
+                .into(),
+            ),
+            (
+                &team,
+                ExecuteMsg::CreateVesting {
+                    beneficiary: bob.to_string(),
+                    schedule,
+                    revoker: None,
+                },
+                events::VestingCreated {
+                    creator: team.clone(),
+                    beneficiary: bob.clone(),
+                    amount: Uint128::new(1_000),
+                }
+                .into(),
+            ),
+            (
+                &creator,
+                ExecuteMsg::RevokeRole {
+                    role: Role::VestingAdmin,
+                    address: team.to_string(),
+                },
+                events::RoleRevoked {
+                    role: "vesting_admin".to_string(),
+                    address: team.clone(),
+                }
+                .into(),
+            ),
+            (
+                &creator,
+                ExecuteMsg::Pause {
+                    operations: Some(vec![Operation::Burns, Operation::Minting]),
+                },
+                events::Paused {
+                    operations: "burns,minting".to_string(),
+                    by: creator.clone(),
+                }
+                .into(),
+            ),
+            (
+                &creator,
+                ExecuteMsg::Unpause {
+                    operations: Some(vec![Operation::Burns, Operation::Minting]),
+                },
+                events::Unpaused {
+                    operations: "burns,minting".to_string(),
+                    by: creator.clone(),
+                }
+                .into(),
+            ),
+            (
+                &creator,
+                ExecuteMsg::FreezeAccount {
+                    address: carol.to_string(),
+                    reason: "court order".to_string(),
+                },
+                events::AccountFrozen {
+                    address: carol.clone(),
+                    reason: "court order".to_string(),
+                    by: creator.clone(),
+                }
+                .into(),
+            ),
+            (
+                &creator,
+                ExecuteMsg::UnfreezeAccount { address: carol.to_string() },
+                events::AccountUnfrozen {
+                    address: carol.clone(),
+                    by: creator.clone(),
+                }
+                .into(),
+            ),
+            (
+                &creator,
+                ExecuteMsg::UpdateFeeConfig {
+                    rate_bps: 0,
+                    treasury_share_bps: 10_000,
+                    burn_share_bps: 0,
+                    pool_share_bps: 0,
+                },
+                events::FeeConfigUpdated {
+                    rate_bps: 0,
+                    treasury_share_bps: 10_000,
+                    burn_share_bps: 0,
+                    pool_share_bps: 0,
+                }
+                .into(),
+            ),
+            (
+                &creator,
+                ExecuteMsg::SetFeeExempt {
+                    address: alice.to_string(),
+                    exempt: true,
+                },
+                events::FeeExemptionUpdated {
+                    address: alice.clone(),
+                    exempt: true,
+                }
+                .into(),
+            ),
+            (
+                &team,
+                ExecuteMsg::IncreaseAllowance {
+                    spender: alice.to_string(),
+                    amount: Uint128::new(1_000),
+                    expires: None,
+                },
+                events::AllowanceIncreased {
+                    owner: team.clone(),
+                    spender: alice.clone(),
+                    amount: Uint128::new(1_000),
+                }
+                .into(),
+            ),
+            (
+                &team,
+                ExecuteMsg::DecreaseAllowance {
+                    spender: alice.to_string(),
+                    amount: Uint128::new(100),
+                    expires: None,
+                },
+                events::AllowanceDecreased {
+                    owner: team.clone(),
+                    spender: alice.clone(),
+                    amount: Uint128::new(100),
+                }
+                .into(),
+            ),
+            (
+                &alice,
+                ExecuteMsg::TransferFrom {
+                    owner: team.to_string(),
+                    recipient: bob.to_string(),
+                    amount: Uint128::new(300),
+                },
+                events::TransferFrom {
+                    owner: team.clone(),
+                    spender: alice.clone(),
+                    to: bob.clone(),
+                    amount: Uint128::new(300),
+                    fee: Uint128::zero(),
+                    fee_treasury: Uint128::zero(),
+                    fee_burned: Uint128::zero(),
+                    fee_pool: Uint128::zero(),
+                }
+                .into(),
+            ),
+            (
+                &alice,
+                ExecuteMsg::BurnFrom {
+                    owner: team.to_string(),
+                    amount: Uint128::new(200),
+                },
+                events::BurnFrom {
+                    owner: team.clone(),
+                    spender: alice.clone(),
+                    amount: Uint128::new(200),
+                }
+                .into(),
+            ),
+            (
+                &team,
+                ExecuteMsg::Send {
+                    contract: receiver.to_string(),
+                    amount: Uint128::new(50),
+                    msg: Binary::default(),
+                },
+                events::Send {
+                    from: team.clone(),
+                    contract: receiver.clone(),
+                    amount: Uint128::new(50),
+                    fee: Uint128::zero(),
+                    fee_treasury: Uint128::zero(),
+                    fee_burned: Uint128::zero(),
+                    fee_pool: Uint128::zero(),
+                }
+                .into(),
+            ),
+            (
+                &creator,
+                ExecuteMsg::ProposeNewOwner {
+                    new_owner: bob.to_string(),
+                    expiry: None,
+                },
+                events::OwnershipProposed {
+                    owner: creator.clone(),
+                    pending_owner: bob.clone(),
+                }
+                .into(),
+            ),
+            (
+                &creator,
+                ExecuteMsg::CancelOwnershipTransfer {},
+                events::OwnershipTransferCancelled { owner: creator.clone() }.into(),
+            ),
+            (
+                &creator,
+                ExecuteMsg::ProposeNewOwner {
+                    new_owner: alice.to_string(),
+                    expiry: None,
+                },
+                events::OwnershipProposed {
+                    owner: creator.clone(),
+                    pending_owner: alice.clone(),
+                }
+                .into(),
+            ),
+            (
+                &alice,
+                ExecuteMsg::AcceptOwnership {},
+                events::OwnershipAccepted { owner: alice.clone() }.into(),
+            ),
+            (
+                &alice,
+                ExecuteMsg::UpdateChannelAllowlist {
+                    channel_id: "channel-0".to_string(),
+                    allowed: true,
+                },
+                events::ChannelAllowlistUpdated {
+                    channel: "channel-0".to_string(),
+                    allowed: true,
+                }
+                .into(),
+            ),
+            (
+                &alice,
+                ExecuteMsg::RenounceOwnership {},
+                events::OwnershipRenounced { owner: alice.clone() }.into(),
+            ),
+        ];
+
+        let mut env = mock_env();
+        env.block.time = env.block.time.plus_seconds(6 * MONTH);
+        for (sender, msg, event) in cases {
+            let res = execute(deps.as_mut(), env.clone(), message_info(sender, &[]), msg).unwrap();
+            assert_eq!(vec![event], res.events);
+        }
     }
+}
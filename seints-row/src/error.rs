@@ -1,4 +1,5 @@
 use cosmwasm_std::{StdError, Uint128};
+use cw_utils::PaymentError;
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -6,6 +7,9 @@ pub enum ContractError {
     #[error("{0}")]
     Std(#[from] StdError),
 
+    #[error("{0}")]
+    Payment(#[from] PaymentError),
+
     /// Occurs when the token decimals are invalid (e.g., greater than 18).
     #[error("Invalid decimals: {decimals} (must be <= 18)")]
     InvalidDecimals { decimals: u8 },
@@ -18,6 +22,18 @@ pub enum ContractError {
     #[error("Unauthorized")]
     Unauthorized {},
 
+    /// Occurs when a privileged action is attempted by an address lacking the role it requires.
+    #[error("Missing role: {role}")]
+    MissingRole { role: String },
+
+    /// Occurs when an operation is attempted while a pauser has paused it.
+    #[error("Operation paused: {operation}")]
+    Paused { operation: String },
+
+    /// Occurs when a frozen account would send, receive or spend tokens.
+    #[error("Account frozen: {address}")]
+    AccountFrozen { address: String },
+
     /// Occurs when a user tries to transfer more tokens than they have.
     #[error("Insufficient balance: required {required}, available {available}")]
     InsufficientBalance { required: Uint128, available: Uint128 },
@@ -30,14 +46,18 @@ pub enum ContractError {
     #[error("Invalid amount: {amount}")]
     InvalidAmount { amount: Uint128 },
 
-    /// Occurs when the token decimals are invalid (e.g., greater than 18).
-    #[error("Invalid decimals: {decimals} (must be <= 18)")]
-    InvalidDecimals { decimals: u8 },
-
-    /// Occurs when the initial supply is invalid (e.g., not exactly 1 billion).
+    /// Occurs when the initial supply does not match the sum of the genesis allocations.
     #[error("Invalid initial supply: {actual} (expected {expected})")]
     InvalidInitialSupply { expected: Uint128, actual: Uint128 },
 
+    /// Occurs when minting more than the cap or the minting schedule allows.
+    #[error("Cannot mint {required}, only {available} can be minted")]
+    MintLimitExceeded { required: Uint128, available: Uint128 },
+
+    /// Occurs when minting or changing the minter after minting has been disabled.
+    #[error("Minting is disabled")]
+    MintingDisabled {},
+
     /// Occurs when an arithmetic operation overflows or underflows.
     #[error("Arithmetic overflow/underflow")]
     Overflow {},
@@ -46,9 +66,169 @@ pub enum ContractError {
     #[error("Invalid metadata URL: {url} (must be a valid URL)")]
     InvalidMetadata { url: String },
 
-    /// Occurs when duplicate addresses are provided (e.g., team and pool addresses are the same).
-    #[error("Duplicate addresses: {address}")]
-    DuplicateAddresses { address: String },
+    /// Occurs when a vesting curve is empty, out of order, decreasing or vests nothing.
+    #[error("Invalid vesting schedule")]
+    InvalidVestingSchedule {},
+
+    /// Occurs when a beneficiary already has a vesting record that is not fully released.
+    #[error("Vesting already exists for {address}")]
+    VestingExists { address: String },
+
+    /// Occurs when revoking a vesting record that has no revoker.
+    #[error("Vesting for {address} is not revocable")]
+    NotRevocable { address: String },
+
+    /// Occurs when releasing for an address that has neither a vesting nor a pool release record.
+    #[error("No vesting or pool release for {address}")]
+    NoVesting { address: String },
+
+    /// Occurs when accepting or cancelling an ownership transfer that was never proposed.
+    #[error("No ownership transfer is pending")]
+    NoPendingOwner {},
+
+    /// Occurs when accepting an ownership transfer after its expiry.
+    #[error("Ownership transfer has expired")]
+    OwnershipTransferExpired {},
+
+    /// Occurs when the fee rate is too high, the shares do not add up to 10,000, or a pool share is set
+    /// without a pool.
+    #[error("Invalid fee configuration")]
+    InvalidFeeConfig {},
+
+    /// Occurs when claiming staking rewards or unbonded tokens while none are due.
+    #[error("Nothing to claim")]
+    NothingToClaim {},
+
+    /// Occurs when funding staking rewards over a duration too long to pay out anything per second.
+    #[error("Invalid reward period: {amount} over {duration} seconds")]
+    InvalidRewardPeriod { amount: Uint128, duration: u64 },
+
+    /// Occurs when a batch transfer has no transfers.
+    #[error("Batch is empty")]
+    EmptyBatch {},
+
+    /// Occurs when a batch transfer has more transfers than the configured maximum.
+    #[error("Batch of {size} transfers exceeds the maximum of {max}")]
+    BatchTooLarge { size: u64, max: u32 },
+
+    /// Occurs when setting a maximum batch size of zero or above the hard cap.
+    #[error("Maximum batch size must be between 1 and {limit}, got {max_batch_size}")]
+    InvalidMaxBatchSize { max_batch_size: u32, limit: u32 },
+
+    /// Occurs when registering an airdrop stage number that is already in use.
+    #[error("Airdrop stage {stage} already exists")]
+    StageExists { stage: u8 },
+
+    /// Occurs when a merkle root or proof element is not a hex encoded 32 byte hash.
+    #[error("Invalid merkle hash: {hash}")]
+    InvalidMerkleHash { hash: String },
+
+    /// Occurs when a merkle proof does not lead to the stage's root.
+    #[error("Invalid merkle proof")]
+    InvalidProof {},
+
+    /// Occurs when an address claims an airdrop stage a second time.
+    #[error("{address} has already claimed stage {stage}")]
+    AlreadyClaimed { stage: u8, address: String },
+
+    /// Occurs when claiming from an airdrop stage after its expiration.
+    #[error("Airdrop stage {stage} has expired")]
+    StageExpired { stage: u8 },
+
+    /// Occurs when reclaiming an airdrop stage before its expiration.
+    #[error("Airdrop stage {stage} has not expired")]
+    StageNotExpired { stage: u8 },
+
+    /// Occurs when a permit's signature or public key does not match its owner.
+    #[error("Invalid permit signature")]
+    InvalidSignature {},
+
+    /// Occurs when a permit uses a nonce other than the owner's next one, such as when it is replayed.
+    #[error("Invalid permit nonce: expected {expected}, got {actual}")]
+    InvalidNonce { expected: u64, actual: u64 },
+
+    /// Occurs when a permit is submitted after its expiry.
+    #[error("Permit has expired")]
+    PermitExpired {},
+
+    /// Occurs when a permit was signed for another chain or contract.
+    #[error("Permit was signed for chain {chain_id} and contract {contract}")]
+    WrongPermitDomain { chain_id: String, contract: String },
+
+    /// Occurs when converting to or from the native denom before it has been created.
+    #[error("Native denom has not been created")]
+    NativeDenomNotSet {},
+
+    /// Occurs when creating the native denom a second time.
+    #[error("Native denom {denom} already exists")]
+    NativeDenomExists { denom: String },
+
+    /// Occurs when converting would leave the escrow out of step with the native supply, or would escrow more than
+    /// the total supply.
+    #[error("Supply invariant violated: escrowed {escrowed}, total supply {total_supply}")]
+    SupplyInvariant { escrowed: Uint128, total_supply: Uint128 },
+
+    /// Occurs when opening an IBC channel that is not unordered.
+    #[error("Only unordered channels are supported")]
+    InvalidIbcOrder {},
+
+    /// Occurs when opening an IBC channel with a version other than `ics20-1`.
+    #[error("Invalid IBC channel version: {version}")]
+    InvalidIbcVersion { version: String },
+
+    /// Occurs when a channel that is not on the owner's allowlist opens or carries a transfer.
+    #[error("Channel {channel} is not allowed")]
+    ChannelNotAllowed { channel: String },
+
+    /// Occurs when transferring over a channel that has not completed its handshake.
+    #[error("Channel {channel} is not connected")]
+    UnknownChannel { channel: String },
+
+    /// Occurs when closing an IBC channel, which would strand the tokens escrowed for it.
+    #[error("IBC channels cannot be closed")]
+    CannotCloseChannel {},
+
+    /// Occurs when an incoming transfer is not this token returning through the channel it left by.
+    #[error("Invalid denom: {denom}")]
+    InvalidDenom { denom: String },
+
+    /// Occurs when migrating from a contract that is not a version of this token.
+    #[error("Cannot migrate from contract {name}")]
+    WrongContract { name: String },
+
+    /// Occurs when migrating to a version older than the one currently stored.
+    #[error("Cannot migrate from version {stored} to older version {current}")]
+    CannotDowngrade { stored: String, current: String },
+
+    /// Occurs when a stored or compiled contract version is not valid semver.
+    #[error("Semver parsing error: {0}")]
+    SemVer(String),
+
+    /// Occurs when an account tries to grant an allowance to itself.
+    #[error("Cannot set allowance for own account")]
+    CannotSetOwnAccount {},
+
+    /// Occurs when a spender uses an allowance that does not exist.
+    #[error("No allowance for this account")]
+    NoAllowance {},
+
+    /// Occurs when a spender tries to move more tokens than its allowance permits.
+    #[error("Insufficient allowance: required {required}, available {available}")]
+    InsufficientAllowance { required: Uint128, available: Uint128 },
+
+    /// Occurs when a spender uses an allowance that has already expired.
+    #[error("Allowance is expired")]
+    Expired {},
+
+    /// Occurs when an allowance is given an expiration that is already in the past.
+    #[error("Invalid expiration value")]
+    InvalidExpiration {},
+}
+
+impl From<semver::Error> for ContractError {
+    fn from(err: semver::Error) -> Self {
+        Self::SemVer(err.to_string())
+    }
 }
 
 #[cfg(test)]
@@ -128,4 +308,25 @@ mod tests {
         };
         assert_eq!(err.to_string(), "Duplicate addresses: team_address");
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_insufficient_allowance_error() {
+        let err = ContractError::InsufficientAllowance {
+            required: Uint128::new(100),
+            available: Uint128::new(10),
+        };
+        assert_eq!(
+            err.to_string(),
+            "Insufficient allowance: required 100, available 10"
+        );
+    }
+
+    #[test]
+    fn test_cannot_downgrade_error() {
+        let err = ContractError::CannotDowngrade {
+            stored: "0.3.0".to_string(),
+            current: "0.2.0".to_string(),
+        };
+        assert_eq!(err.to_string(), "Cannot migrate from version 0.3.0 to older version 0.2.0");
+    }
+}
@@ -0,0 +1,437 @@
+//! Typed events emitted by every handler, for indexers.
+//!
+//! Each struct is emitted as a single `Event` of type `seints.<name>` whose attributes are exactly its fields,
+//! in declaration order, so the key set of an event never varies. Amounts are in the token's base unit,
+//! addresses are validated, and times are Unix seconds.
+
+use cosmwasm_std::{Addr, Event, Uint128};
+
+/// Defines event structs together with their event type and their conversion into an `Event`.
+macro_rules! events {
+    ($(
+        $(#[$doc:meta])*
+        $name:ident = $ty:literal {
+            $($(#[$field_doc:meta])* $field:ident: $field_ty:ty),* $(,)?
+        }
+    )*) => {$(
+        $(#[$doc])*
+        #[derive(Clone, Debug, PartialEq)]
+        pub struct $name {
+            $($(#[$field_doc])* pub $field: $field_ty,)*
+        }
+
+        impl $name {
+            /// The type this event is emitted under.
+            pub const TYPE: &'static str = concat!("seints.", $ty);
+        }
+
+        impl From<$name> for Event {
+            fn from(event: $name) -> Self {
+                Event::new($name::TYPE)$(.add_attribute(stringify!($field), event.$field.to_string()))*
+            }
+        }
+    )*};
+}
+
+events! {
+    /// The contract was instantiated.
+    Instantiated = "instantiated" {
+        owner: Addr,
+        total_supply: Uint128,
+        metadata_url: String,
+    }
+
+    /// The contract was migrated to a new version.
+    Migrated = "migrated" {
+        from_contract: String,
+        from_version: String,
+        to_version: String,
+    }
+
+    /// Tokens moved between balances. `amount` is what left `from`; `to` received `amount` minus `fee`.
+    Transfer = "transfer" {
+        from: Addr,
+        to: Addr,
+        amount: Uint128,
+        /// Total transfer fee, split into the three parts below.
+        fee: Uint128,
+        fee_treasury: Uint128,
+        fee_burned: Uint128,
+        fee_pool: Uint128,
+    }
+
+    /// Tokens moved from one sender to several recipients, each also reported in its own `transfer` event.
+    /// The fee keys add up the fees of those transfers.
+    BatchTransfer = "batch_transfer" {
+        from: Addr,
+        recipients: u64,
+        total: Uint128,
+        fee: Uint128,
+        fee_treasury: Uint128,
+        fee_burned: Uint128,
+        fee_pool: Uint128,
+    }
+
+    /// Tokens moved to a contract, which was notified through its `Receive` hook.
+    Send = "send" {
+        from: Addr,
+        contract: Addr,
+        amount: Uint128,
+        fee: Uint128,
+        fee_treasury: Uint128,
+        fee_burned: Uint128,
+        fee_pool: Uint128,
+    }
+
+    /// A spender moved tokens out of `owner`'s balance through an allowance.
+    TransferFrom = "transfer_from" {
+        owner: Addr,
+        spender: Addr,
+        to: Addr,
+        amount: Uint128,
+        fee: Uint128,
+        fee_treasury: Uint128,
+        fee_burned: Uint128,
+        fee_pool: Uint128,
+    }
+
+    /// The most recipients of a batch transfer changed.
+    MaxBatchSizeUpdated = "max_batch_size_updated" {
+        max_batch_size: u32,
+    }
+
+    /// Tokens were burned from the sender's balance.
+    Burn = "burn" {
+        from: Addr,
+        amount: Uint128,
+    }
+
+    /// A spender burned tokens from `owner`'s balance through an allowance.
+    BurnFrom = "burn_from" {
+        owner: Addr,
+        spender: Addr,
+        amount: Uint128,
+    }
+
+    /// New tokens were minted.
+    Mint = "mint" {
+        minter: Addr,
+        to: Addr,
+        amount: Uint128,
+    }
+
+    /// Minting moved to another minter, or was disabled when `new_minter` is `none`.
+    MinterUpdated = "minter_updated" {
+        new_minter: String,
+    }
+
+    /// A beneficiary released its own vested tokens.
+    VestingReleased = "vesting_released" {
+        beneficiary: Addr,
+        amount: Uint128,
+    }
+
+    /// The pool released its own vested tokens.
+    PoolReleased = "pool_released" {
+        beneficiary: Addr,
+        amount: Uint128,
+    }
+
+    /// A keeper released vested tokens for a beneficiary and was paid `tip`.
+    ReleasedFor = "released_for" {
+        keeper: Addr,
+        beneficiary: Addr,
+        amount: Uint128,
+        tip: Uint128,
+    }
+
+    /// A keeper released vested tokens for several beneficiaries and was paid `tip`.
+    BatchReleased = "batch_released" {
+        keeper: Addr,
+        /// Beneficiaries that were given.
+        beneficiaries: u64,
+        /// Beneficiaries that had something to release.
+        released: u64,
+        /// Beneficiaries that could not be released, each reported in a `release_skipped` event.
+        skipped: u64,
+        amount: Uint128,
+        tip: Uint128,
+    }
+
+    /// A beneficiary of a batch release was skipped. `reason` is `no_record`, `frozen` or `paused`.
+    ReleaseSkipped = "release_skipped" {
+        keeper: Addr,
+        beneficiary: Addr,
+        reason: String,
+    }
+
+    /// Tokens were moved into the keeper reserve.
+    KeeperReserveFunded = "keeper_reserve_funded" {
+        from: Addr,
+        amount: Uint128,
+        reserve: Uint128,
+    }
+
+    /// The keeper tip changed.
+    KeeperTipUpdated = "keeper_tip_updated" {
+        tip: Uint128,
+        min_release: Uint128,
+    }
+
+    /// Tokens were locked in a new vesting record.
+    VestingCreated = "vesting_created" {
+        creator: Addr,
+        beneficiary: Addr,
+        amount: Uint128,
+    }
+
+    /// A vesting record was revoked. The vested part went to the beneficiary and the rest to the treasury.
+    VestingRevoked = "vesting_revoked" {
+        beneficiary: Addr,
+        revoker: Addr,
+        paid_out: Uint128,
+        clawed_back: Uint128,
+        treasury: Addr,
+    }
+
+    /// The metadata URL changed.
+    MetadataUpdated = "metadata_updated" {
+        metadata_url: String,
+    }
+
+    /// The owner proposed an ownership transfer.
+    OwnershipProposed = "ownership_proposed" {
+        owner: Addr,
+        pending_owner: Addr,
+    }
+
+    /// The pending owner accepted ownership.
+    OwnershipAccepted = "ownership_accepted" {
+        owner: Addr,
+    }
+
+    /// The owner cancelled a pending ownership transfer.
+    OwnershipTransferCancelled = "ownership_transfer_cancelled" {
+        owner: Addr,
+    }
+
+    /// The owner gave up ownership for good.
+    OwnershipRenounced = "ownership_renounced" {
+        owner: Addr,
+    }
+
+    /// A role was granted.
+    RoleGranted = "role_granted" {
+        role: String,
+        address: Addr,
+    }
+
+    /// A role was revoked.
+    RoleRevoked = "role_revoked" {
+        role: String,
+        address: Addr,
+    }
+
+    /// Operations were paused. `operations` is a comma separated list.
+    Paused = "paused" {
+        operations: String,
+        by: Addr,
+    }
+
+    /// Operations were unpaused. `operations` is a comma separated list.
+    Unpaused = "unpaused" {
+        operations: String,
+        by: Addr,
+    }
+
+    /// An account was frozen.
+    AccountFrozen = "account_frozen" {
+        address: Addr,
+        reason: String,
+        by: Addr,
+    }
+
+    /// An account was unfrozen.
+    AccountUnfrozen = "account_unfrozen" {
+        address: Addr,
+        by: Addr,
+    }
+
+    /// The transfer fee changed.
+    FeeConfigUpdated = "fee_config_updated" {
+        rate_bps: u16,
+        treasury_share_bps: u16,
+        burn_share_bps: u16,
+        pool_share_bps: u16,
+    }
+
+    /// An address became exempt from the transfer fee, or stopped being exempt.
+    FeeExemptionUpdated = "fee_exemption_updated" {
+        address: Addr,
+        exempt: bool,
+    }
+
+    /// A holder delegated its voting weight.
+    Delegated = "delegated" {
+        delegator: Addr,
+        delegatee: Addr,
+        weight: Uint128,
+    }
+
+    /// Tokens were staked. `staked` is the staker's stake afterwards.
+    Staked = "staked" {
+        staker: Addr,
+        amount: Uint128,
+        staked: Uint128,
+    }
+
+    /// Tokens started unbonding and can be claimed from `release_at`.
+    Unstaked = "unstaked" {
+        staker: Addr,
+        amount: Uint128,
+        release_at: u64,
+    }
+
+    /// Unbonded tokens returned to the staker's balance.
+    UnbondedClaimed = "unbonded_claimed" {
+        staker: Addr,
+        amount: Uint128,
+    }
+
+    /// Staking rewards were paid out.
+    RewardsClaimed = "rewards_claimed" {
+        staker: Addr,
+        amount: Uint128,
+    }
+
+    /// The pool funded staking rewards, paid out at `reward_rate` per second until `period_finish`.
+    RewardsFunded = "rewards_funded" {
+        funder: Addr,
+        amount: Uint128,
+        reward_rate: Uint128,
+        period_finish: u64,
+    }
+
+    /// An airdrop stage was registered and funded.
+    AirdropRegistered = "airdrop_registered" {
+        stage: u8,
+        root: String,
+        total_amount: Uint128,
+    }
+
+    /// An address claimed its airdrop.
+    AirdropClaimed = "airdrop_claimed" {
+        stage: u8,
+        address: Addr,
+        amount: Uint128,
+    }
+
+    /// What was left of an expired airdrop stage went back to its funder.
+    AirdropReclaimed = "airdrop_reclaimed" {
+        stage: u8,
+        recipient: Addr,
+        amount: Uint128,
+    }
+
+    /// The native denom wrapping the token was created.
+    NativeDenomCreated = "native_denom_created" {
+        denom: String,
+    }
+
+    /// Tokens were escrowed and the same amount of the native denom minted.
+    ConvertedToNative = "converted_to_native" {
+        address: Addr,
+        denom: String,
+        amount: Uint128,
+    }
+
+    /// The native denom was burned and the same amount of tokens released from escrow.
+    ConvertedFromNative = "converted_from_native" {
+        address: Addr,
+        denom: String,
+        amount: Uint128,
+    }
+
+    /// Tokens were escrowed and sent over an IBC channel.
+    IbcTransfer = "ibc_transfer" {
+        from: Addr,
+        channel: String,
+        to: String,
+        amount: Uint128,
+    }
+
+    /// A channel was added to or removed from the IBC allowlist.
+    ChannelAllowlistUpdated = "channel_allowlist_updated" {
+        channel: String,
+        allowed: bool,
+    }
+
+    /// An IBC channel completed its handshake.
+    IbcChannelConnected = "ibc_channel_connected" {
+        channel: String,
+    }
+
+    /// Tokens returned over an IBC channel and were released from escrow.
+    IbcReceived = "ibc_received" {
+        channel: String,
+        receiver: Addr,
+        amount: Uint128,
+    }
+
+    /// An incoming IBC transfer was rejected and acknowledged as an error.
+    IbcReceiveFailed = "ibc_receive_failed" {
+        channel: String,
+        error: String,
+    }
+
+    /// The counterparty accepted an outgoing IBC transfer.
+    IbcAcknowledged = "ibc_acknowledged" {
+        channel: String,
+    }
+
+    /// An outgoing IBC transfer failed or timed out and was refunded.
+    IbcRefunded = "ibc_refunded" {
+        channel: String,
+        sender: Addr,
+        amount: Uint128,
+        reason: String,
+    }
+
+    /// A spender's allowance grew.
+    AllowanceIncreased = "allowance_increased" {
+        owner: Addr,
+        spender: Addr,
+        amount: Uint128,
+    }
+
+    /// A spender's allowance shrank.
+    AllowanceDecreased = "allowance_decreased" {
+        owner: Addr,
+        spender: Addr,
+        amount: Uint128,
+    }
+
+    /// A relayer submitted a holder's permit. The action it performed emits its own event.
+    PermitUsed = "permit_used" {
+        owner: Addr,
+        nonce: u64,
+        relayer: Addr,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::attr;
+
+    #[test]
+    fn keys_follow_field_order() {
+        let event: Event = Burn {
+            from: Addr::unchecked("alice"),
+            amount: Uint128::new(5),
+        }
+        .into();
+        assert_eq!("seints.burn", event.ty);
+        assert_eq!(vec![attr("from", "alice"), attr("amount", "5")], event.attributes);
+    }
+}
@@ -1,10 +1,11 @@
+use cosmwasm_schema::cw_serde;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use cosmwasm_std::{
-    to_json_binary, Addr, BankMsg, Coin, CosmosMsg, CustomQuery, Querier, QuerierWrapper, StdError,
-    StdResult, WasmMsg, WasmQuery,
+    to_json_binary, Addr, Binary, Coin, CosmosMsg, CustomQuery, Querier, QuerierWrapper, StdError,
+    StdResult, Uint128, WasmMsg, WasmQuery,
 };
-use crate::msg::{ExecuteMsg, GetCountResponse, QueryMsg};
+use crate::msg::{BalanceResponse, ExecuteMsg, QueryMsg};
 
 /// A wrapper around a contract address that provides helper functions
 /// for interacting with the contract.
@@ -24,11 +25,11 @@ impl CwTemplateContract {
     /// * `funds` - Optional coins to send along with the message (default is empty).
     ///
     /// # Returns
-    /// A `StdResult<CosmosMsg>` containing the message to execute.
+    /// A `StdResult<CosmosMsg<C>>` containing the message to execute, for a chain with custom messages `C`.
     ///
     /// # Errors
     /// Returns an error if serialization of the message fails.
-    pub fn call<T: Into<ExecuteMsg>>(&self, msg: T, funds: Vec<Coin>) -> StdResult<CosmosMsg> {
+    pub fn call<T: Into<ExecuteMsg>, C>(&self, msg: T, funds: Vec<Coin>) -> StdResult<CosmosMsg<C>> {
         let msg = to_json_binary(&msg.into()).map_err(|e| {
             StdError::generic_err(format!("Failed to serialize message: {}", e))
         })?;
@@ -40,24 +41,27 @@ impl CwTemplateContract {
         .into())
     }
 
-    /// Queries the contract to get the current count.
+    /// Queries the token balance of an address.
     ///
     /// # Arguments
     /// * `querier` - A reference to a querier implementing the `Querier` trait.
+    /// * `address` - The address whose balance is requested.
     ///
     /// # Returns
-    /// A `StdResult<GetCountResponse>` containing the current count.
+    /// A `StdResult<BalanceResponse>` containing the balance.
     ///
     /// # Errors
     /// Returns an error if:
     /// - Serialization of the query message fails.
     /// - The query execution fails.
-    pub fn count<Q, CQ>(&self, querier: &Q) -> StdResult<GetCountResponse>
+    pub fn balance<Q, CQ>(&self, querier: &Q, address: impl Into<String>) -> StdResult<BalanceResponse>
     where
         Q: Querier,
         CQ: CustomQuery,
     {
-        let msg = QueryMsg::GetCount {};
+        let msg = QueryMsg::Balance {
+            address: address.into(),
+        };
         let query = WasmQuery::Smart {
             contract_addr: self.addr().into(),
             msg: to_json_binary(&msg).map_err(|e| {
@@ -65,9 +69,51 @@ impl CwTemplateContract {
             })?,
         }
         .into();
-        let res: GetCountResponse = QuerierWrapper::<CQ>::new(querier)
+        let res: BalanceResponse = QuerierWrapper::<CQ>::new(querier)
             .query(&query)
             .map_err(|e| StdError::generic_err(format!("Query failed: {}", e)))?;
         Ok(res)
     }
-}
\ No newline at end of file
+}
+
+/// The payload delivered to a contract when tokens are sent to it with `ExecuteMsg::Send`.
+/// `sender` is the account that sent the tokens and `msg` is forwarded untouched.
+#[cw_serde]
+pub struct Cw20ReceiveMsg {
+    pub sender: String,
+    pub amount: Uint128,
+    pub msg: Binary,
+}
+
+impl Cw20ReceiveMsg {
+    /// Serializes the message wrapped in `ReceiverExecuteMsg::Receive`.
+    ///
+    /// # Errors
+    /// Returns an error if serialization of the message fails.
+    pub fn into_json_binary(self) -> StdResult<Binary> {
+        to_json_binary(&ReceiverExecuteMsg::Receive(self))
+    }
+
+    /// Creates a `CosmosMsg` that delivers this message to `contract_addr`.
+    ///
+    /// # Arguments
+    /// * `contract_addr` - The address of the receiving contract.
+    ///
+    /// # Errors
+    /// Returns an error if serialization of the message fails.
+    pub fn into_cosmos_msg<T: Into<String>, C>(self, contract_addr: T) -> StdResult<CosmosMsg<C>> {
+        Ok(WasmMsg::Execute {
+            contract_addr: contract_addr.into(),
+            msg: self.into_json_binary()?,
+            funds: vec![],
+        }
+        .into())
+    }
+}
+
+/// The execute message a receiving contract must accept to be the target of `ExecuteMsg::Send`.
+/// Receivers usually embed this variant in their own `ExecuteMsg`.
+#[cw_serde]
+pub enum ReceiverExecuteMsg {
+    Receive(Cw20ReceiveMsg),
+}
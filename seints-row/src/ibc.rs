@@ -0,0 +1,540 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    from_json, to_json_binary, Addr, Binary, DepsMut, Env, Event, IbcBasicResponse, IbcChannel, IbcChannelCloseMsg,
+    IbcChannelConnectMsg, IbcChannelOpenMsg, IbcChannelOpenResponse, IbcEndpoint, IbcOrder, IbcPacket,
+    IbcPacketAckMsg, IbcPacketReceiveMsg, IbcPacketTimeoutMsg, IbcReceiveResponse, Never, Storage, Uint128,
+};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::contract::{credit, credit_refund, ensure_not_paused};
+use crate::error::ContractError;
+use crate::events;
+use crate::state::{ChannelInfo, Operation, CHANNELS, CHANNEL_ALLOWLIST, CHANNEL_ESCROW};
+
+/// Channel version spoken on both ends.
+pub const ICS20_VERSION: &str = "ics20-1";
+/// ICS-20 channels are unordered.
+pub const ICS20_ORDERING: IbcOrder = IbcOrder::Unordered;
+/// Seconds an outgoing transfer may wait to be relayed when the sender sets no timeout.
+pub const DEFAULT_TIMEOUT_SECONDS: u64 = 3_600;
+
+/// The ICS-20 `FungibleTokenPacketData`, as sent and received over a channel.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Ics20Packet {
+    /// Serialized as a string, as ICS-20 requires.
+    pub amount: Uint128,
+    /// The denom on the sending chain, prefixed with the port and channel it last arrived through.
+    pub denom: String,
+    pub receiver: String,
+    pub sender: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+}
+
+/// The ICS-20 acknowledgement: a single byte on success, or an error message.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Ics20Ack {
+    Result(Binary),
+    Error(String),
+}
+
+/// Returns the denom this token travels under: `cw20:` followed by the contract address.
+pub fn denom(contract: &Addr) -> String {
+    format!("cw20:{contract}")
+}
+
+fn ack_success() -> Binary {
+    to_json_binary(&Ics20Ack::Result(Binary::from(vec![1]))).unwrap()
+}
+
+fn ack_fail(err: String) -> Binary {
+    to_json_binary(&Ics20Ack::Error(err)).unwrap()
+}
+
+/// Accepts only unordered `ics20-1` channels the owner has allowlisted.
+fn validate_channel(
+    storage: &dyn Storage,
+    channel: &IbcChannel,
+    counterparty_version: Option<&str>,
+) -> Result<(), ContractError> {
+    if channel.order != ICS20_ORDERING {
+        return Err(ContractError::InvalidIbcOrder {});
+    }
+    for version in [Some(channel.version.as_str()), counterparty_version].into_iter().flatten() {
+        if version != ICS20_VERSION {
+            return Err(ContractError::InvalidIbcVersion { version: version.to_string() });
+        }
+    }
+    if !CHANNEL_ALLOWLIST.has(storage, &channel.endpoint.channel_id) {
+        return Err(ContractError::ChannelNotAllowed { channel: channel.endpoint.channel_id.clone() });
+    }
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn ibc_channel_open(
+    deps: DepsMut,
+    _env: Env,
+    msg: IbcChannelOpenMsg,
+) -> Result<IbcChannelOpenResponse, ContractError> {
+    validate_channel(deps.storage, msg.channel(), msg.counterparty_version())?;
+    Ok(None)
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn ibc_channel_connect(
+    deps: DepsMut,
+    _env: Env,
+    msg: IbcChannelConnectMsg,
+) -> Result<IbcBasicResponse, ContractError> {
+    validate_channel(deps.storage, msg.channel(), msg.counterparty_version())?;
+
+    let channel = msg.channel();
+    let info = ChannelInfo {
+        counterparty_endpoint: channel.counterparty_endpoint.clone(),
+        connection_id: channel.connection_id.clone(),
+    };
+    CHANNELS.save(deps.storage, &channel.endpoint.channel_id, &info)?;
+
+    Ok(IbcBasicResponse::new().add_event(Event::from(events::IbcChannelConnected {
+        channel: channel.endpoint.channel_id.clone(),
+    })))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn ibc_channel_close(
+    _deps: DepsMut,
+    _env: Env,
+    _msg: IbcChannelCloseMsg,
+) -> Result<IbcBasicResponse, ContractError> {
+    // Tokens escrowed for the channel could never come back
+    Err(ContractError::CannotCloseChannel {})
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn ibc_packet_receive(
+    deps: DepsMut,
+    env: Env,
+    msg: IbcPacketReceiveMsg,
+) -> Result<IbcReceiveResponse, Never> {
+    // Failures are acknowledged as errors so the sending chain refunds the sender
+    match receive(deps, env, &msg.packet) {
+        Ok(response) => Ok(response),
+        Err(err) => {
+            let event = events::IbcReceiveFailed {
+                channel: msg.packet.dest.channel_id,
+                error: err.to_string(),
+            };
+            Ok(IbcReceiveResponse::new(ack_fail(err.to_string())).add_event(Event::from(event)))
+        }
+    }
+}
+
+/// Releases tokens returning from the counterparty chain to their receiver.
+/// Only tokens that left through this channel can come back; nothing is written unless all checks pass.
+fn receive(deps: DepsMut, env: Env, packet: &IbcPacket) -> Result<IbcReceiveResponse, ContractError> {
+    ensure_not_paused(deps.storage, Operation::Transfers)?;
+    let data: Ics20Packet = from_json(&packet.data)?;
+    let channel = &packet.dest.channel_id;
+    if !CHANNEL_ALLOWLIST.has(deps.storage, channel) {
+        return Err(ContractError::ChannelNotAllowed { channel: channel.clone() });
+    }
+
+    // Returning vouchers carry the prefix the counterparty added when they arrived there
+    let IbcEndpoint { port_id, channel_id } = &packet.src;
+    let expected = format!("{port_id}/{channel_id}/{}", denom(&env.contract.address));
+    if data.denom != expected {
+        return Err(ContractError::InvalidDenom { denom: data.denom });
+    }
+
+    let escrowed = CHANNEL_ESCROW.may_load(deps.storage, channel)?.unwrap_or_default();
+    let remaining = escrowed
+        .checked_sub(data.amount)
+        .map_err(|_| ContractError::InsufficientBalance { required: data.amount, available: escrowed })?;
+    let receiver = deps.api.addr_validate(&data.receiver)?;
+    credit(deps.storage, &receiver, data.amount, env.block.height)?;
+    CHANNEL_ESCROW.save(deps.storage, channel, &remaining)?;
+
+    Ok(IbcReceiveResponse::new(ack_success()).add_event(Event::from(events::IbcReceived {
+        channel: channel.clone(),
+        receiver,
+        amount: data.amount,
+    })))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn ibc_packet_ack(
+    deps: DepsMut,
+    env: Env,
+    msg: IbcPacketAckMsg,
+) -> Result<IbcBasicResponse, ContractError> {
+    let ack: Ics20Ack = from_json(&msg.acknowledgement.data)?;
+    match ack {
+        Ics20Ack::Result(_) => Ok(IbcBasicResponse::new().add_event(Event::from(events::IbcAcknowledged {
+            channel: msg.original_packet.src.channel_id,
+        }))),
+        Ics20Ack::Error(err) => refund(deps, env, &msg.original_packet, &err),
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn ibc_packet_timeout(
+    deps: DepsMut,
+    env: Env,
+    msg: IbcPacketTimeoutMsg,
+) -> Result<IbcBasicResponse, ContractError> {
+    refund(deps, env, &msg.packet, "timeout")
+}
+
+/// Returns the tokens of a packet the counterparty did not accept to its sender, even if the sender has been
+/// frozen since.
+fn refund(deps: DepsMut, env: Env, packet: &IbcPacket, reason: &str) -> Result<IbcBasicResponse, ContractError> {
+    let data: Ics20Packet = from_json(&packet.data)?;
+    let channel = &packet.src.channel_id;
+    let escrowed = CHANNEL_ESCROW.may_load(deps.storage, channel)?.unwrap_or_default();
+    let remaining = escrowed
+        .checked_sub(data.amount)
+        .map_err(|_| ContractError::InsufficientBalance { required: data.amount, available: escrowed })?;
+    CHANNEL_ESCROW.save(deps.storage, channel, &remaining)?;
+    let sender = deps.api.addr_validate(&data.sender)?;
+    credit_refund(deps.storage, &sender, data.amount, env.block.height)?;
+
+    Ok(IbcBasicResponse::new().add_event(Event::from(events::IbcRefunded {
+        channel: channel.clone(),
+        sender,
+        amount: data.amount,
+        reason: reason.to_string(),
+    })))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::contract::{execute, instantiate, query};
+    use crate::msg::{ExecuteMsg, GenesisAllocation, InstantiateMsg, ListChannelsResponse, QueryMsg};
+    use crate::state::BALANCES;
+    use crate::token_factory::SeiMsg;
+    use cosmwasm_std::testing::{
+        message_info, mock_dependencies, mock_env, mock_ibc_channel_connect_ack, mock_ibc_channel_open_init,
+        mock_ibc_packet_ack, mock_ibc_packet_recv, mock_ibc_packet_timeout, MockApi, MockQuerier, MockStorage,
+    };
+    use cosmwasm_std::{CosmosMsg, IbcAcknowledgement, IbcMsg, IbcTimeout, OwnedDeps, Timestamp};
+    use std::collections::BTreeMap;
+
+    const CHANNEL: &str = "channel-0";
+    /// The endpoint the mock helpers connect `CHANNEL` to.
+    const COUNTERPARTY_PORT: &str = "their-port";
+    const COUNTERPARTY_CHANNEL: &str = "channel-1234";
+
+    /// A minimal ICS-20 chain at the other end of `CHANNEL`. It holds the vouchers minted for tokens that
+    /// arrived from the contract, by holder.
+    #[derive(Default)]
+    struct Counterparty {
+        vouchers: BTreeMap<String, Uint128>,
+        sequence: u64,
+    }
+
+    impl Counterparty {
+        fn voucher_denom() -> String {
+            format!("{COUNTERPARTY_PORT}/{COUNTERPARTY_CHANNEL}/{}", denom(&mock_env().contract.address))
+        }
+
+        fn endpoint() -> IbcEndpoint {
+            IbcEndpoint {
+                port_id: COUNTERPARTY_PORT.to_string(),
+                channel_id: COUNTERPARTY_CHANNEL.to_string(),
+            }
+        }
+
+        /// Mints vouchers for a packet from the contract, as the transfer module would.
+        fn receive(&mut self, packet: &IbcPacket) -> Binary {
+            let data: Ics20Packet = from_json(&packet.data).unwrap();
+            *self.vouchers.entry(data.receiver).or_default() += data.amount;
+            ack_success()
+        }
+
+        /// Burns `amount` vouchers of `sender` and returns the packet sending them back to `receiver`.
+        fn send_back(&mut self, sender: &str, receiver: &Addr, amount: u128) -> IbcPacket {
+            let balance = self.vouchers.get_mut(sender).unwrap();
+            *balance = balance.checked_sub(Uint128::new(amount)).unwrap();
+            self.sequence += 1;
+            let data = Ics20Packet {
+                amount: Uint128::new(amount),
+                denom: Self::voucher_denom(),
+                receiver: receiver.to_string(),
+                sender: sender.to_string(),
+                memo: None,
+            };
+            let dest = IbcEndpoint {
+                port_id: format!("wasm.{}", mock_env().contract.address),
+                channel_id: CHANNEL.to_string(),
+            };
+            let timeout = IbcTimeout::with_timestamp(mock_env().block.time.plus_seconds(60));
+            IbcPacket::new(to_json_binary(&data).unwrap(), Self::endpoint(), dest, self.sequence, timeout)
+        }
+
+        /// Refunds the vouchers of a packet the contract acknowledged with an error.
+        fn acknowledge(&mut self, packet: &IbcPacket, ack: &Binary) {
+            if let Ics20Ack::Error(_) = from_json(ack).unwrap() {
+                let data: Ics20Packet = from_json(&packet.data).unwrap();
+                *self.vouchers.entry(data.sender).or_default() += data.amount;
+            }
+        }
+    }
+
+    /// Relays a packet the contract sent to the counterparty and its acknowledgement back, or times it out if the
+    /// counterparty's clock has passed its timeout.
+    fn relay_out(
+        deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>,
+        counterparty: &mut Counterparty,
+        msg: &CosmosMsg<SeiMsg>,
+        now: Timestamp,
+    ) -> IbcBasicResponse {
+        let CosmosMsg::Ibc(IbcMsg::SendPacket { channel_id, data, timeout }) = msg else {
+            panic!("expected a packet");
+        };
+        let src = IbcEndpoint {
+            port_id: format!("wasm.{}", mock_env().contract.address),
+            channel_id: channel_id.clone(),
+        };
+        let packet = IbcPacket::new(data.clone(), src, Counterparty::endpoint(), 1, timeout.clone());
+        let relayer = deps.api.addr_make("relayer");
+        if timeout.timestamp().is_some_and(|timeout| timeout <= now) {
+            let msg = IbcPacketTimeoutMsg::new(packet, relayer);
+            return ibc_packet_timeout(deps.as_mut(), mock_env(), msg).unwrap();
+        }
+        let ack = IbcAcknowledgement::new(counterparty.receive(&packet));
+        ibc_packet_ack(deps.as_mut(), mock_env(), IbcPacketAckMsg::new(ack, packet, relayer)).unwrap()
+    }
+
+    /// Relays a packet from the counterparty to the contract and the contract's acknowledgement back.
+    fn relay_in(
+        deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>,
+        counterparty: &mut Counterparty,
+        packet: IbcPacket,
+    ) -> IbcReceiveResponse {
+        let relayer = deps.api.addr_make("relayer");
+        let res = ibc_packet_receive(deps.as_mut(), mock_env(), IbcPacketReceiveMsg::new(packet.clone(), relayer))
+            .unwrap();
+        counterparty.acknowledge(&packet, res.acknowledgement.as_ref().unwrap());
+        res
+    }
+
+    /// Instantiates the token with all of its supply held by `holder`, and connects `CHANNEL`.
+    fn setup(deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>) -> Addr {
+        let owner = deps.api.addr_make("owner");
+        let holder = deps.api.addr_make("holder");
+        let msg = InstantiateMsg {
+            name: "$SEINTS".to_string(),
+            symbol: "SEINTS".to_string(),
+            decimals: 6,
+            initial_supply: Uint128::new(1_000_000),
+            metadata_url: "https://example.com/logo.webp".to_string(),
+            allocations: vec![GenesisAllocation {
+                address: holder.to_string(),
+                amount: Uint128::new(1_000_000),
+                vesting: None,
+                revoker: None,
+            }],
+            pool_address: None,
+            treasury: None,
+            mint: None,
+            vesting_votes: false,
+            unbonding_period: 0,
+        };
+        instantiate(deps.as_mut(), mock_env(), message_info(&owner, &[]), msg).unwrap();
+
+        // Channels must be allowlisted before they can open
+        let open = mock_ibc_channel_open_init(CHANNEL, ICS20_ORDERING, ICS20_VERSION);
+        let err = ibc_channel_open(deps.as_mut(), mock_env(), open.clone()).unwrap_err();
+        assert!(matches!(err, ContractError::ChannelNotAllowed { .. }));
+        let msg = ExecuteMsg::UpdateChannelAllowlist {
+            channel_id: CHANNEL.to_string(),
+            allowed: true,
+        };
+        execute(deps.as_mut(), mock_env(), message_info(&owner, &[]), msg).unwrap();
+        ibc_channel_open(deps.as_mut(), mock_env(), open).unwrap();
+        let connect = mock_ibc_channel_connect_ack(CHANNEL, ICS20_ORDERING, ICS20_VERSION);
+        ibc_channel_connect(deps.as_mut(), mock_env(), connect).unwrap();
+        holder
+    }
+
+    /// Sends `amount` from `holder` over `CHANNEL` and returns the packet data.
+    fn transfer(deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>, holder: &Addr, amount: u128) -> Ics20Packet {
+        let msg = ExecuteMsg::IbcTransfer {
+            channel: CHANNEL.to_string(),
+            remote_address: "remote1".to_string(),
+            amount: Uint128::new(amount),
+            timeout: None,
+        };
+        let res = execute(deps.as_mut(), mock_env(), message_info(holder, &[]), msg).unwrap();
+        let CosmosMsg::Ibc(IbcMsg::SendPacket { channel_id, data, .. }) = &res.messages[0].msg else {
+            panic!("expected a packet");
+        };
+        assert_eq!(CHANNEL, channel_id);
+        let event = events::IbcTransfer {
+            from: holder.clone(),
+            channel: CHANNEL.to_string(),
+            to: "remote1".to_string(),
+            amount: Uint128::new(amount),
+        };
+        assert_eq!(vec![Event::from(event)], res.events);
+        from_json(data).unwrap()
+    }
+
+    #[test]
+    fn failed_transfers_are_refunded() {
+        let mut deps = mock_dependencies();
+        let holder = setup(&mut deps);
+
+        let packet = transfer(&mut deps, &holder, 1_000);
+        assert_eq!(denom(&mock_env().contract.address), packet.denom);
+        assert_eq!(Uint128::new(999_000), BALANCES.load(deps.as_ref().storage, &holder).unwrap());
+        assert_eq!(Uint128::new(1_000), CHANNEL_ESCROW.load(deps.as_ref().storage, CHANNEL).unwrap());
+
+        // An error acknowledgement refunds the sender
+        let ack = IbcAcknowledgement::new(ack_fail("bad receiver".to_string()));
+        let msg = mock_ibc_packet_ack(CHANNEL, &packet, ack).unwrap();
+        let res = ibc_packet_ack(deps.as_mut(), mock_env(), msg).unwrap();
+        assert_eq!(Uint128::new(1_000_000), BALANCES.load(deps.as_ref().storage, &holder).unwrap());
+        let event = events::IbcRefunded {
+            channel: CHANNEL.to_string(),
+            sender: holder.clone(),
+            amount: Uint128::new(1_000),
+            reason: "bad receiver".to_string(),
+        };
+        assert_eq!(vec![Event::from(event)], res.events);
+
+        // So does a timeout
+        let packet = transfer(&mut deps, &holder, 500);
+        let msg = mock_ibc_packet_timeout(CHANNEL, &packet).unwrap();
+        ibc_packet_timeout(deps.as_mut(), mock_env(), msg).unwrap();
+        assert_eq!(Uint128::new(1_000_000), BALANCES.load(deps.as_ref().storage, &holder).unwrap());
+        assert_eq!(Uint128::zero(), CHANNEL_ESCROW.load(deps.as_ref().storage, CHANNEL).unwrap());
+
+        // Senders frozen while their transfer was in flight are refunded too, rather than leaving it in escrow
+        let packet = transfer(&mut deps, &holder, 200);
+        let owner = deps.api.addr_make("owner");
+        let msg = ExecuteMsg::FreezeAccount {
+            address: holder.to_string(),
+            reason: "investigation".to_string(),
+        };
+        execute(deps.as_mut(), mock_env(), message_info(&owner, &[]), msg).unwrap();
+        let msg = mock_ibc_packet_timeout(CHANNEL, &packet).unwrap();
+        ibc_packet_timeout(deps.as_mut(), mock_env(), msg).unwrap();
+        assert_eq!(Uint128::new(1_000_000), BALANCES.load(deps.as_ref().storage, &holder).unwrap());
+        assert_eq!(Uint128::zero(), CHANNEL_ESCROW.load(deps.as_ref().storage, CHANNEL).unwrap());
+    }
+
+    #[test]
+    fn returning_tokens_are_released_from_escrow() {
+        let mut deps = mock_dependencies();
+        let holder = setup(&mut deps);
+        let receiver = deps.api.addr_make("receiver");
+        let packet = transfer(&mut deps, &holder, 1_000);
+        let ack = IbcAcknowledgement::new(ack_success());
+        ibc_packet_ack(deps.as_mut(), mock_env(), mock_ibc_packet_ack(CHANNEL, &packet, ack).unwrap()).unwrap();
+
+        // The counterparty sends back part of it under its voucher denom
+        let returning = Ics20Packet {
+            amount: Uint128::new(400),
+            denom: format!("their-port/channel-1234/{}", packet.denom),
+            receiver: receiver.to_string(),
+            sender: "remote1".to_string(),
+            memo: None,
+        };
+        let msg = mock_ibc_packet_recv(CHANNEL, &returning).unwrap();
+        let res = ibc_packet_receive(deps.as_mut(), mock_env(), msg).unwrap();
+        assert_eq!(Some(ack_success()), res.acknowledgement);
+        let event = events::IbcReceived {
+            channel: CHANNEL.to_string(),
+            receiver: receiver.clone(),
+            amount: Uint128::new(400),
+        };
+        assert_eq!(vec![Event::from(event)], res.events);
+        assert_eq!(Uint128::new(400), BALANCES.load(deps.as_ref().storage, &receiver).unwrap());
+        assert_eq!(Uint128::new(600), CHANNEL_ESCROW.load(deps.as_ref().storage, CHANNEL).unwrap());
+
+        // Anything else is acknowledged as an error without touching balances
+        let foreign = Ics20Packet {
+            denom: "their-port/channel-1234/uatom".to_string(),
+            ..returning.clone()
+        };
+        let msg = mock_ibc_packet_recv(CHANNEL, &foreign).unwrap();
+        let res = ibc_packet_receive(deps.as_mut(), mock_env(), msg).unwrap();
+        assert_eq!(events::IbcReceiveFailed::TYPE, res.events[0].ty);
+        let ack: Ics20Ack = from_json(res.acknowledgement.unwrap()).unwrap();
+        assert!(matches!(ack, Ics20Ack::Error(_)));
+        let too_much = Ics20Packet {
+            amount: Uint128::new(601),
+            ..returning
+        };
+        let msg = mock_ibc_packet_recv(CHANNEL, &too_much).unwrap();
+        let res = ibc_packet_receive(deps.as_mut(), mock_env(), msg).unwrap();
+        let ack: Ics20Ack = from_json(res.acknowledgement.unwrap()).unwrap();
+        assert!(matches!(ack, Ics20Ack::Error(_)));
+        assert_eq!(Uint128::new(400), BALANCES.load(deps.as_ref().storage, &receiver).unwrap());
+
+        let msg = QueryMsg::ListChannels {
+            start_after: None,
+            limit: None,
+        };
+        let channels: ListChannelsResponse = from_json(query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+        assert_eq!(1, channels.channels.len());
+        assert_eq!(Uint128::new(600), channels.channels[0].escrowed);
+    }
+
+    #[test]
+    fn transfers_round_trip_through_a_relayer() {
+        let mut deps = mock_dependencies();
+        let holder = setup(&mut deps);
+        let receiver = deps.api.addr_make("receiver");
+        let mut counterparty = Counterparty::default();
+        let now = mock_env().block.time;
+
+        // Out to the counterparty, which mints vouchers and acknowledges
+        let msg = ExecuteMsg::IbcTransfer {
+            channel: CHANNEL.to_string(),
+            remote_address: "remote1".to_string(),
+            amount: Uint128::new(1_000),
+            timeout: None,
+        };
+        let res = execute(deps.as_mut(), mock_env(), message_info(&holder, &[]), msg).unwrap();
+        let res = relay_out(&mut deps, &mut counterparty, &res.messages[0].msg, now);
+        let event = events::IbcAcknowledged { channel: CHANNEL.to_string() };
+        assert_eq!(vec![Event::from(event)], res.events);
+        assert_eq!(Uint128::new(1_000), counterparty.vouchers["remote1"]);
+        assert_eq!(Uint128::new(999_000), BALANCES.load(deps.as_ref().storage, &holder).unwrap());
+
+        // Part of it comes back to another account and is released from escrow
+        let packet = counterparty.send_back("remote1", &receiver, 400);
+        let res = relay_in(&mut deps, &mut counterparty, packet);
+        assert_eq!(Some(ack_success()), res.acknowledgement);
+        assert_eq!(Uint128::new(400), BALANCES.load(deps.as_ref().storage, &receiver).unwrap());
+        assert_eq!(Uint128::new(600), CHANNEL_ESCROW.load(deps.as_ref().storage, CHANNEL).unwrap());
+        assert_eq!(Uint128::new(600), counterparty.vouchers["remote1"]);
+
+        // Sending back to an invalid receiver fails there and the counterparty refunds its vouchers
+        let packet = counterparty.send_back("remote1", &Addr::unchecked("not-an-address"), 100);
+        relay_in(&mut deps, &mut counterparty, packet);
+        assert_eq!(Uint128::new(600), counterparty.vouchers["remote1"]);
+        assert_eq!(Uint128::new(600), CHANNEL_ESCROW.load(deps.as_ref().storage, CHANNEL).unwrap());
+
+        // A packet relayed too late times out and is refunded here
+        let msg = ExecuteMsg::IbcTransfer {
+            channel: CHANNEL.to_string(),
+            remote_address: "remote1".to_string(),
+            amount: Uint128::new(300),
+            timeout: Some(60),
+        };
+        let res = execute(deps.as_mut(), mock_env(), message_info(&holder, &[]), msg).unwrap();
+        let res = relay_out(&mut deps, &mut counterparty, &res.messages[0].msg, now.plus_seconds(61));
+        assert_eq!(events::IbcRefunded::TYPE, res.events[0].ty);
+        assert_eq!(Uint128::new(999_000), BALANCES.load(deps.as_ref().storage, &holder).unwrap());
+        assert_eq!(Uint128::new(600), CHANNEL_ESCROW.load(deps.as_ref().storage, CHANNEL).unwrap());
+        assert_eq!(Uint128::new(600), counterparty.vouchers["remote1"]);
+    }
+}
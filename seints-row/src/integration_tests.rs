@@ -1,33 +1,44 @@
 #[cfg(test)]
 mod tests {
     use crate::helpers::CwTemplateContract;
-use crate::msg::{InstantiateMsg, ExecuteMsg};
-use cosmwasm_std::testing::MockApi;
-use cosmwasm_std::{Addr, Coin, Empty, Uint128};
-use cw_multi_test::{App, AppBuilder, Contract, ContractWrapper, Executor};
+use crate::msg::{GenesisAllocation, InstantiateMsg, ExecuteMsg};
+use crate::token_factory::SeiMsg;
+use cosmwasm_std::testing::{MockApi, MockStorage};
+use cosmwasm_std::{to_json_binary, Addr, Coin, Empty, Uint128};
+use cw_multi_test::{App, AppBuilder, BankKeeper, Contract, ContractWrapper, Executor, WasmKeeper};
+
+/// The mock chain, with `mock_token_factory::TokenFactory` handling the contract's custom messages.
+type TokenApp = App<BankKeeper, MockApi, MockStorage, mock_token_factory::TokenFactory, WasmKeeper<SeiMsg, Empty>>;
 
 /// Returns a boxed instance of the contract to be used in testing.
-pub fn contract_template() -> Box<dyn Contract<Empty>> {
+pub fn contract_template() -> Box<dyn Contract<SeiMsg>> {
     let contract = ContractWrapper::new(
         crate::contract::execute,
         crate::contract::instantiate,
         crate::contract::query,
-    );
+    )
+    .with_migrate(crate::contract::migrate);
     Box::new(contract)
 }
 
 const USER: &str = "USER";
 const ADMIN: &str = "ADMIN";
+const POOL: &str = "POOL";
 const NATIVE_DENOM: &str = "denom";
 
+/// Returns the address the mock chain uses for the given account name.
+fn addr(name: &str) -> Addr {
+    MockApi::default().addr_make(name)
+}
+
 /// Mocks the blockchain environment and initializes the user's balance.
-fn mock_app() -> App {
-    AppBuilder::new().build(|router, _, storage| {
+fn mock_app() -> TokenApp {
+    AppBuilder::new_custom().with_custom(mock_token_factory::TokenFactory).build(|router, _, storage| {
         router
             .bank
             .init_balance(
                 storage,
-                &MockApi::default().addr_validate(USER).unwrap(),
+                &addr(USER),
                 vec![Coin {
                     denom: NATIVE_DENOM.to_string(),
                     amount: Uint128::new(1),
@@ -38,10 +49,11 @@ fn mock_app() -> App {
 }
 
 /// Instantiates the contract and ensures proper initialization.
-fn proper_instantiate() -> (App, CwTemplateContract) {
+/// `USER` receives 20% of the supply and `POOL` the remaining 80%.
+fn proper_instantiate() -> (TokenApp, CwTemplateContract) {
     let mut app = mock_app();
     let cw_template_id = app.store_code(contract_template());
-    let user = app.api().addr_validate(USER).unwrap();
+    let user = addr(USER);
     assert_eq!(
         app.wrap().query_balance(user.clone(), NATIVE_DENOM).unwrap().amount,
         Uint128::new(1)
@@ -50,13 +62,32 @@ fn proper_instantiate() -> (App, CwTemplateContract) {
         name: "SEINT".to_string(),
         symbol: "SEINT".to_string(),
         decimals: 6,
-        initial_supply: Uint128::new(1_000_000_000_000_000),
-        specified_address: "0x45d2B456361f5D2D3e473018E56482059075eceB".to_string(),
+        initial_supply: Uint128::new(1_000_000_000),
+        metadata_url: "https://example.com/logo.webp".to_string(),
+        allocations: vec![
+            GenesisAllocation {
+                address: user.to_string(),
+                amount: Uint128::new(200_000_000),
+                vesting: None,
+                revoker: None,
+            },
+            GenesisAllocation {
+                address: addr(POOL).to_string(),
+                amount: Uint128::new(800_000_000),
+                vesting: None,
+                revoker: None,
+            },
+        ],
+        pool_address: Some(addr(POOL).to_string()),
+        treasury: None,
+        mint: None,
+        vesting_votes: false,
+        unbonding_period: 0,
     };
     let cw_template_contract_addr = app
         .instantiate_contract(
             cw_template_id,
-            Addr::unchecked(ADMIN),
+            addr(ADMIN),
             &msg,
             &[],
             "test",
@@ -74,13 +105,13 @@ mod token_tests {
     fn test_transfer() {
         let (mut app, cw_template_contract) = proper_instantiate();
         // Transfer 100 units from USER to another address
-        let recipient = "recipient".to_string();
+        let recipient = addr("recipient").to_string();
         let msg = ExecuteMsg::Transfer {
             recipient: recipient.clone(),
             amount: Uint128::new(100),
         };
-        let cosmos_msg = cw_template_contract.call(msg).unwrap();
-        app.execute(Addr::unchecked(USER), cosmos_msg).unwrap();
+        let cosmos_msg = cw_template_contract.call(msg, vec![]).unwrap();
+        app.execute(addr(USER), cosmos_msg).unwrap();
 
         // Check balances
         let recipient_balance: Uint128 = app
@@ -97,13 +128,13 @@ mod token_tests {
             .query_wasm_smart(
                 cw_template_contract.addr(),
                 &crate::msg::QueryMsg::GetBalance {
-                    address: USER.to_string(),
+                    address: addr(USER).to_string(),
                 },
             )
             .unwrap();
         assert_eq!(
             user_balance,
-            Uint128::new(800_000_000_000_000 - 100), // 80% of total supply minus transferred amount
+            Uint128::new(200_000_000 - 100), // 20% of total supply minus transferred amount
             "User balance mismatch"
         );
     }
@@ -115,8 +146,8 @@ mod token_tests {
         let msg = ExecuteMsg::Burn {
             amount: Uint128::new(100),
         };
-        let cosmos_msg = cw_template_contract.call(msg).unwrap();
-        app.execute(Addr::unchecked(USER), cosmos_msg).unwrap();
+        let cosmos_msg = cw_template_contract.call(msg, vec![]).unwrap();
+        app.execute(addr(USER), cosmos_msg).unwrap();
 
         // Check user balance
         let user_balance: Uint128 = app
@@ -124,18 +155,18 @@ mod token_tests {
             .query_wasm_smart(
                 cw_template_contract.addr(),
                 &crate::msg::QueryMsg::GetBalance {
-                    address: USER.to_string(),
+                    address: addr(USER).to_string(),
                 },
             )
             .unwrap();
         assert_eq!(
             user_balance,
-            Uint128::new(800_000_000_000_000 - 100),
+            Uint128::new(200_000_000 - 100),
             "User balance mismatch after burn"
         );
 
         // Check total supply
-        let token_info: crate::msg::TokenInfoResponse = app
+        let token_info: crate::msg::GetTokenInfoResponse = app
             .wrap()
             .query_wasm_smart(
                 cw_template_contract.addr(),
@@ -144,7 +175,7 @@ mod token_tests {
             .unwrap();
         assert_eq!(
             token_info.total_supply,
-            Uint128::new(1_000_000_000_000_000 - 100),
+            Uint128::new(1_000_000_000 - 100),
             "Total supply mismatch after burn"
         );
     }
@@ -153,14 +184,246 @@ mod token_tests {
     fn test_transfer_insufficient_balance() {
         let (mut app, cw_template_contract) = proper_instantiate();
         // Attempt to transfer more tokens than the user has
-        let recipient = "recipient".to_string();
+        let recipient = addr("recipient").to_string();
         let msg = ExecuteMsg::Transfer {
             recipient: recipient.clone(),
-            amount: Uint128::new(900_000_000_000_000), // More than the user's balance
+            amount: Uint128::new(900_000_000), // More than the user's balance
         };
-        let cosmos_msg = cw_template_contract.call(msg).unwrap();
-        let err = app.execute(Addr::unchecked(USER), cosmos_msg).unwrap_err();
-        assert_eq!(err.to_string(), "Insufficient balance", "Error message mismatch");
+        let cosmos_msg = cw_template_contract.call(msg, vec![]).unwrap();
+        let err = app.execute(addr(USER), cosmos_msg).unwrap_err();
+        assert_eq!(
+            err.root_cause().to_string(),
+            "Insufficient balance: required 900000000, available 200000000",
+            "Error message mismatch"
+        );
+    }
+}
+
+/// A minimal contract implementing the `Receive` hook, used as the target of `Send`.
+mod receiver {
+    use crate::helpers::{Cw20ReceiveMsg, ReceiverExecuteMsg};
+    use crate::token_factory::SeiMsg;
+    use cosmwasm_std::{
+        to_json_binary, Binary, Deps, DepsMut, Empty, Env, MessageInfo, Response, StdError, StdResult,
+    };
+    use cw_multi_test::{Contract, ContractWrapper};
+    use cw_storage_plus::Item;
+
+    /// The last payload delivered to the receiver.
+    const LAST_RECEIVED: Item<Cw20ReceiveMsg> = Item::new("last_received");
+
+    fn instantiate(_deps: DepsMut, _env: Env, _info: MessageInfo, _msg: Empty) -> StdResult<Response> {
+        Ok(Response::new())
+    }
+
+    fn execute(deps: DepsMut, _env: Env, _info: MessageInfo, msg: ReceiverExecuteMsg) -> StdResult<Response> {
+        let ReceiverExecuteMsg::Receive(receive_msg) = msg;
+        // Lets tests check that a failing hook reverts the whole send
+        if receive_msg.msg == to_json_binary("reject")? {
+            return Err(StdError::generic_err("Receiver rejected the tokens"));
+        }
+        LAST_RECEIVED.save(deps.storage, &receive_msg)?;
+        Ok(Response::new())
+    }
+
+    fn query(deps: Deps, _env: Env, _msg: Empty) -> StdResult<Binary> {
+        to_json_binary(&LAST_RECEIVED.may_load(deps.storage)?)
+    }
+
+    /// Returns a boxed instance of the receiver contract.
+    pub fn contract() -> Box<dyn Contract<SeiMsg>> {
+        Box::new(ContractWrapper::new_with_empty(execute, instantiate, query))
+    }
+}
+
+mod send_tests {
+    use super::*;
+    use crate::helpers::Cw20ReceiveMsg;
+
+    /// Instantiates the token and the mock receiver, returning the receiver's address.
+    fn setup_with_receiver() -> (TokenApp, CwTemplateContract, Addr) {
+        let (mut app, cw_template_contract) = proper_instantiate();
+        let receiver_id = app.store_code(receiver::contract());
+        let receiver_addr = app
+            .instantiate_contract(receiver_id, addr(ADMIN), &Empty {}, &[], "receiver", None)
+            .unwrap();
+        (app, cw_template_contract, receiver_addr)
     }
+
+    fn balance(app: &TokenApp, token: &CwTemplateContract, address: &Addr) -> Uint128 {
+        app.wrap()
+            .query_wasm_smart(
+                token.addr(),
+                &crate::msg::QueryMsg::GetBalance {
+                    address: address.to_string(),
+                },
+            )
+            .unwrap()
+    }
+
+    #[test]
+    fn test_send_calls_receive_hook() {
+        let (mut app, cw_template_contract, receiver_addr) = setup_with_receiver();
+        let payload = to_json_binary("stake").unwrap();
+        let msg = ExecuteMsg::Send {
+            contract: receiver_addr.to_string(),
+            amount: Uint128::new(1_000),
+            msg: payload.clone(),
+        };
+        let cosmos_msg = cw_template_contract.call(msg, vec![]).unwrap();
+        app.execute(addr(USER), cosmos_msg).unwrap();
+
+        // The receiver is credited and learns who sent the tokens
+        assert_eq!(balance(&app, &cw_template_contract, &receiver_addr), Uint128::new(1_000));
+        let received: Option<Cw20ReceiveMsg> = app
+            .wrap()
+            .query_wasm_smart(receiver_addr, &Empty {})
+            .unwrap();
+        assert_eq!(
+            received,
+            Some(Cw20ReceiveMsg {
+                sender: addr(USER).to_string(),
+                amount: Uint128::new(1_000),
+                msg: payload,
+            })
+        );
+    }
+
+    #[test]
+    fn test_send_reverts_when_hook_fails() {
+        let (mut app, cw_template_contract, receiver_addr) = setup_with_receiver();
+        let msg = ExecuteMsg::Send {
+            contract: receiver_addr.to_string(),
+            amount: Uint128::new(1_000),
+            msg: to_json_binary("reject").unwrap(),
+        };
+        let cosmos_msg = cw_template_contract.call(msg, vec![]).unwrap();
+        app.execute(addr(USER), cosmos_msg).unwrap_err();
+
+        // Neither balance moved because the hook error aborts the transaction
+        assert_eq!(balance(&app, &cw_template_contract, &receiver_addr), Uint128::zero());
+        assert_eq!(
+            balance(&app, &cw_template_contract, &addr(USER)),
+            Uint128::new(200_000_000)
+        );
+    }
+}
+
+/// A stand-in for the chain's token factory module, backed by the mock bank.
+mod mock_token_factory {
+    use crate::token_factory::SeiMsg;
+    use anyhow::Result as AnyResult;
+    use cosmwasm_std::{
+        Addr, Api, BankMsg, Binary, BlockInfo, CosmosMsg, CustomMsg, CustomQuery, Empty, Querier, Storage,
+    };
+    use cw_multi_test::{AppResponse, BankSudo, CosmosRouter, Module, SudoMsg};
+    use serde::de::DeserializeOwned;
+
+    pub struct TokenFactory;
+
+    impl Module for TokenFactory {
+        type ExecT = SeiMsg;
+        type QueryT = Empty;
+        type SudoT = Empty;
+
+        fn execute<ExecC, QueryC>(
+            &self,
+            api: &dyn Api,
+            storage: &mut dyn Storage,
+            router: &dyn CosmosRouter<ExecC = ExecC, QueryC = QueryC>,
+            block: &BlockInfo,
+            sender: Addr,
+            msg: SeiMsg,
+        ) -> AnyResult<AppResponse>
+        where
+            ExecC: CustomMsg + DeserializeOwned + 'static,
+            QueryC: CustomQuery + DeserializeOwned + 'static,
+        {
+            match msg {
+                // The mock bank accepts any denom, so there is nothing to create
+                SeiMsg::CreateDenom { .. } => Ok(AppResponse::default()),
+                // Like Sei's module, mint to and burn from the calling contract
+                SeiMsg::MintTokens { amount } => {
+                    let mint = BankSudo::Mint {
+                        to_address: sender.to_string(),
+                        amount: vec![amount],
+                    };
+                    router.sudo(api, storage, block, SudoMsg::Bank(mint))
+                }
+                SeiMsg::BurnTokens { amount } => {
+                    let burn = BankMsg::Burn { amount: vec![amount] };
+                    router.execute(api, storage, block, sender, CosmosMsg::Bank(burn))?;
+                    Ok(AppResponse::default())
+                }
+            }
+        }
+
+        fn query(
+            &self,
+            _api: &dyn Api,
+            _storage: &dyn Storage,
+            _querier: &dyn Querier,
+            _block: &BlockInfo,
+            _request: Empty,
+        ) -> AnyResult<Binary> {
+            anyhow::bail!("token factory has no queries")
+        }
+
+        fn sudo<ExecC, QueryC>(
+            &self,
+            _api: &dyn Api,
+            _storage: &mut dyn Storage,
+            _router: &dyn CosmosRouter<ExecC = ExecC, QueryC = QueryC>,
+            _block: &BlockInfo,
+            _msg: Empty,
+        ) -> AnyResult<AppResponse>
+        where
+            ExecC: CustomMsg + DeserializeOwned + 'static,
+            QueryC: CustomQuery + DeserializeOwned + 'static,
+        {
+            anyhow::bail!("token factory has no sudo messages")
+        }
+    }
+}
+
+mod native_denom_tests {
+    use super::*;
+    use cosmwasm_std::coins;
+
+    #[test]
+    fn test_convert_round_trip() {
+        let (mut app, cw_template_contract) = proper_instantiate();
+        let msg = ExecuteMsg::CreateNativeDenom { subdenom: "seints".to_string() };
+        let cosmos_msg = cw_template_contract.call(msg, vec![]).unwrap();
+        app.execute(addr(ADMIN), cosmos_msg).unwrap();
+        let denom = format!("factory/{}/seints", cw_template_contract.addr());
+
+        // Converting escrows the tokens and mints the native denom 1:1
+        let msg = ExecuteMsg::ConvertToNative { amount: Uint128::new(1_000) };
+        let cosmos_msg = cw_template_contract.call(msg, vec![]).unwrap();
+        app.execute(addr(USER), cosmos_msg).unwrap();
+        assert_eq!(app.wrap().query_balance(addr(USER), &denom).unwrap().amount, Uint128::new(1_000));
+
+        // Converting back burns the native denom and releases the escrow
+        let msg = ExecuteMsg::ConvertFromNative {};
+        let cosmos_msg = cw_template_contract.call(msg, coins(400, &denom)).unwrap();
+        app.execute(addr(USER), cosmos_msg).unwrap();
+        assert_eq!(app.wrap().query_balance(addr(USER), &denom).unwrap().amount, Uint128::new(600));
+
+        let native: crate::msg::NativeDenomResponse = app
+            .wrap()
+            .query_wasm_smart(cw_template_contract.addr(), &crate::msg::QueryMsg::NativeDenom {})
+            .unwrap();
+        assert_eq!(native.escrowed, app.wrap().query_supply(&denom).unwrap().amount);
+        assert_eq!(app.wrap().query_balance(cw_template_contract.addr(), &denom).unwrap().amount, Uint128::zero());
+        let user_balance: Uint128 = app
+            .wrap()
+            .query_wasm_smart(
+                cw_template_contract.addr(),
+                &crate::msg::QueryMsg::GetBalance { address: addr(USER).to_string() },
+            )
+            .unwrap();
+        assert_eq!(user_balance, Uint128::new(200_000_000 - 600));
+    }
+}
 }
-}
\ No newline at end of file
@@ -1,9 +1,18 @@
+/// Merkle tree hashing and proof verification for airdrops.
+pub mod airdrop;
+
 /// Core contract logic, including instantiation, execution, and query handlers.
 pub mod contract;
 
 /// Error handling for the contract, defining custom errors like `ContractError`.
 mod error;
 
+/// Typed `seints.*` events emitted by the contract.
+pub mod events;
+
+/// ICS-20 channel and packet entry points for moving the token to other chains.
+pub mod ibc;
+
 /// Helper functions and types to simplify interactions with the contract.
 pub mod helpers;
 
@@ -14,8 +23,20 @@ pub mod integration_tests;
 /// Messages used to interact with the contract, such as `InstantiateMsg`, `ExecuteMsg`, and `QueryMsg`.
 pub mod msg;
 
+/// Hashing and signer derivation for off-chain signed permits.
+pub mod permit;
+
+/// Reward-per-token accounting for staking.
+pub mod staking;
+
 /// The contract's storage state, including global variables like balances and metadata.
 pub mod state;
 
+/// Custom message bindings for the chain's token factory module.
+pub mod token_factory;
+
+/// Vesting curves and the release logic shared by owner vesting and pool releases.
+pub mod vesting;
+
 /// Re-export `ContractError` for easy access in other modules.
 pub use crate::error::ContractError;
\ No newline at end of file
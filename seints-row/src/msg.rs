@@ -1,17 +1,66 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::Uint128;
+use cosmwasm_std::{Addr, Binary, IbcEndpoint, Uint128};
+use cw_utils::Expiration;
+
+use crate::state::{Operation, Role, UnbondingEntry};
+use crate::vesting::Schedule;
 
 /// Message to instantiate the contract.
-/// Defines the initial configuration, including token details and distribution addresses.
+/// Defines the initial configuration, including token details and the genesis allocations.
 #[cw_serde]
 pub struct InstantiateMsg {
     pub name: String,
     pub symbol: String,
     pub decimals: u8,
     pub initial_supply: Uint128,
-    pub team_address: String,
-    pub pool_address: String,
     pub metadata_url: String,
+    /// How the initial supply is distributed. The allocations must add up to `initial_supply`.
+    pub allocations: Vec<GenesisAllocation>,
+    /// Allocation whose locked part is released through the pool release schedule.
+    pub pool_address: Option<String>,
+    /// Receives unvested tokens clawed back by `RevokeVesting`. Defaults to the instantiator.
+    pub treasury: Option<String>,
+    /// Allows minting beyond the initial supply. The supply is fixed when unset.
+    pub mint: Option<MinterInfo>,
+    /// Counts still-locked vesting toward the beneficiary's voting weight. Cannot be changed later.
+    #[serde(default)]
+    pub vesting_votes: bool,
+    /// Seconds unstaked tokens wait before they can be claimed.
+    #[serde(default)]
+    pub unbonding_period: u64,
+}
+
+/// Minting settings given at instantiation.
+#[cw_serde]
+pub struct MinterInfo {
+    pub minter: String,
+    /// Upper bound on the total supply, including the initial supply.
+    pub cap: Option<Uint128>,
+    /// Limits how much can be minted over time, counting only tokens minted after instantiation.
+    pub schedule: Option<Schedule>,
+}
+
+/// A share of the initial supply assigned to an address at instantiation.
+#[cw_serde]
+pub struct GenesisAllocation {
+    pub address: String,
+    /// Tokens credited to `address` immediately.
+    pub amount: Uint128,
+    /// Tokens locked for `address` and released over time. The locked amount is the schedule's total.
+    pub vesting: Option<Schedule>,
+    /// Address allowed to revoke the locked tokens. The vesting is irrevocable when unset.
+    pub revoker: Option<String>,
+}
+
+/// Message to migrate the contract to this version.
+/// The fields are only used when upgrading from the legacy layout, which stored no configuration or metadata.
+#[cw_serde]
+#[derive(Default)]
+pub struct MigrateMsg {
+    pub metadata_url: Option<String>,
+    pub pool_address: Option<String>,
+    /// Defaults to the token owner.
+    pub treasury: Option<String>,
 }
 
 /// Messages that can modify the contract's state.
@@ -19,70 +68,658 @@ pub struct InstantiateMsg {
 pub enum ExecuteMsg {
     /// Transfers tokens from the sender to a recipient.
     Transfer { recipient: String, amount: Uint128 },
+    /// Transfers tokens from the sender to several recipients at once, as (recipient, amount) pairs.
+    /// Recipients must be distinct and amounts non-zero; the transfer fee applies to each pair.
+    BatchTransfer { transfers: Vec<(String, Uint128)> },
+    /// Sets the most recipients a single `BatchTransfer` may pay, and the most beneficiaries a single
+    /// `ReleaseBatch` may release for, between 1 and 500 (only callable by the owner).
+    UpdateMaxBatchSize { max_batch_size: u32 },
     /// Burns tokens from the sender's balance.
     Burn { amount: Uint128 },
+    /// Creates `amount` new tokens for `recipient` (only callable by the minter).
+    Mint { recipient: String, amount: Uint128 },
+    /// Hands minting over to `new_minter`, or disables it permanently when unset (only callable by the
+    /// current minter).
+    UpdateMinter { new_minter: Option<String> },
     /// Releases vested tokens for the sender.
     ReleaseVested {},
     /// Releases pool tokens for the sender.
     ReleasePool {},
-    /// Updates the metadata URL (only callable by the owner).
+    /// Releases everything vested for `beneficiary`, from both its vesting and pool release records.
+    /// Anyone can call it: the tokens always go to the beneficiary, and the caller earns the keeper tip.
+    ReleaseFor { beneficiary: String },
+    /// Same as `ReleaseFor`, for several distinct beneficiaries at once, up to the maximum batch size.
+    /// Beneficiaries without a record, frozen, or with paused releases are skipped and reported.
+    ReleaseBatch { beneficiaries: Vec<String> },
+    /// Moves tokens from the sender's balance into the reserve that keeper tips are paid from.
+    FundKeeperReserve { amount: Uint128 },
+    /// Sets the tip paid per beneficiary released on someone else's behalf, and the smallest release that earns
+    /// it (only callable by the owner).
+    UpdateKeeperTip { tip: Uint128, min_release: Uint128 },
+    /// Updates the metadata URL (only callable by a metadata manager).
     UpdateMetadata { metadata_url: String },
+    /// Allows `spender` to move an additional `amount` of the sender's tokens.
+    /// If `expires` is set, it replaces the current expiration of the allowance.
+    IncreaseAllowance {
+        spender: String,
+        amount: Uint128,
+        expires: Option<Expiration>,
+    },
+    /// Lowers the allowance of `spender`, removing it entirely once it reaches zero.
+    DecreaseAllowance {
+        spender: String,
+        amount: Uint128,
+        expires: Option<Expiration>,
+    },
+    /// Transfers tokens from `owner` to `recipient` using the sender's allowance.
+    TransferFrom {
+        owner: String,
+        recipient: String,
+        amount: Uint128,
+    },
+    /// Burns tokens from `owner` using the sender's allowance.
+    BurnFrom { owner: String, amount: Uint128 },
+    /// Transfers tokens to `contract` and calls its `Receive` hook with `msg`.
+    Send {
+        contract: String,
+        amount: Uint128,
+        msg: Binary,
+    },
+    /// Locks the schedule's total from the sender's balance into a new vesting record for `beneficiary`.
+    /// If `revoker` is set, that address can later claw back the unvested part. Only callable by a vesting admin.
+    CreateVesting {
+        beneficiary: String,
+        schedule: Schedule,
+        revoker: Option<String>,
+    },
+    /// Ends a revocable vesting: pays out what has vested and returns the rest to the treasury.
+    /// Only callable by the vesting's revoker, and not while vesting releases are paused. Works on frozen accounts.
+    RevokeVesting { beneficiary: String },
+    /// Proposes `new_owner` as the next owner, replacing any earlier proposal (only callable by the owner).
+    /// The transfer completes once `new_owner` accepts it, before `expiry` if one is set.
+    ProposeNewOwner {
+        new_owner: String,
+        expiry: Option<Expiration>,
+    },
+    /// Accepts a pending ownership transfer (only callable by the proposed owner).
+    AcceptOwnership {},
+    /// Withdraws a pending ownership transfer (only callable by the owner).
+    CancelOwnershipTransfer {},
+    /// Gives up ownership for good, leaving owner-only actions unusable (only callable by the owner).
+    RenounceOwnership {},
+    /// Grants `role` to `address` (only callable by the owner).
+    GrantRole { role: Role, address: String },
+    /// Takes `role` away from `address` (only callable by the owner).
+    RevokeRole { role: Role, address: String },
+    /// Pauses `operations`, or everything when unset (only callable by a pauser).
+    Pause { operations: Option<Vec<Operation>> },
+    /// Resumes `operations`, or everything when unset (only callable by a pauser).
+    Unpause { operations: Option<Vec<Operation>> },
+    /// Blocks every balance change of `address` until it is unfrozen (only callable by a compliance role holder).
+    FreezeAccount { address: String, reason: String },
+    /// Lifts the freeze on `address` (only callable by a compliance role holder).
+    UnfreezeAccount { address: String },
+    /// Sets the transfer fee charged on transfers and sends (only callable by a fee manager).
+    /// `rate_bps` is taken from the amount; the shares split it and must add up to 10,000.
+    UpdateFeeConfig {
+        rate_bps: u16,
+        treasury_share_bps: u16,
+        burn_share_bps: u16,
+        pool_share_bps: u16,
+    },
+    /// Exempts `address` from the transfer fee, or removes the exemption (only callable by a fee manager).
+    /// Transfers are free when either side is exempt.
+    SetFeeExempt { address: String, exempt: bool },
+    /// Delegates the sender's whole voting weight to `delegatee`, which may be the sender itself.
+    /// Tokens stay where they are; the weight follows every later balance change.
+    Delegate { delegatee: String },
+    /// Moves tokens from the sender's balance into its stake, where they earn rewards.
+    Stake { amount: Uint128 },
+    /// Starts unbonding staked tokens. They stop earning rewards at once and can be claimed with
+    /// `ClaimUnbonded` once the unbonding period has passed.
+    Unstake { amount: Uint128 },
+    /// Returns every unbonding entry of the sender whose unbonding period has passed to its balance.
+    ClaimUnbonded {},
+    /// Pays the sender the staking rewards it has earned.
+    ClaimRewards {},
+    /// Moves `amount` from the pool's balance into staking rewards, paid out evenly over the next `duration`
+    /// seconds (only callable by the pool, or the owner on its behalf). Rewards still due from a running period are
+    /// rolled into the new one.
+    FundRewards { amount: Uint128, duration: u64 },
+    /// Registers airdrop `stage`, escrowing `total_amount` from the pool's balance (only callable by the pool, or
+    /// the owner on its behalf).
+    /// `root` is the hex encoded root of a tree built by the `airdrop` bin.
+    RegisterMerkleRoot {
+        stage: u8,
+        root: String,
+        expiration: Expiration,
+        total_amount: Uint128,
+    },
+    /// Claims the sender's `amount` from airdrop `stage`, proven by the hex encoded sibling hashes in `proof`.
+    ClaimAirdrop { stage: u8, amount: Uint128, proof: Vec<String> },
+    /// Creates the token factory denom `factory/{contract}/{subdenom}` that wraps this token (only callable by
+    /// the owner, once).
+    CreateNativeDenom { subdenom: String },
+    /// Escrows `amount` of the sender's tokens and mints the same amount of the native denom to the sender.
+    ConvertToNative { amount: Uint128 },
+    /// Burns the native denom sent along with the message and returns the same amount of escrowed tokens
+    /// to the sender. Bank coins cannot trigger a `Receive` hook, so the funds are attached to this message instead.
+    ConvertFromNative {},
+    /// Sends `amount` of the sender's tokens to `remote_address` on the chain at the other end of `channel`,
+    /// escrowing them until they come back. They are refunded if the transfer fails or is not relayed within
+    /// `timeout` seconds (one hour by default).
+    IbcTransfer {
+        channel: String,
+        remote_address: String,
+        amount: Uint128,
+        timeout: Option<u64>,
+    },
+    /// Allows or disallows `channel_id` to open and carry transfers (only callable by the owner).
+    UpdateChannelAllowlist { channel_id: String, allowed: bool },
+    /// Performs an action a holder authorized off-chain, so a relayer can pay the gas on their behalf.
+    Permit { permit: Permit },
+    /// Returns what is left of an expired airdrop stage to the address that funded it (only callable by the owner).
+    ReclaimAirdrop { stage: u8 },
+}
+
+/// A holder-signed authorization that anyone can submit.
+#[cw_serde]
+pub struct Permit {
+    pub params: PermitParams,
+    /// The holder's compressed secp256k1 public key, which must derive to `params.owner`.
+    pub pubkey: Binary,
+    /// 64 byte signature over the SHA-256 of the JSON serialization of `params`.
+    pub signature: Binary,
+}
+
+/// What a permit authorizes, and the nonce, expiry and domain that keep it from being replayed.
+#[cw_serde]
+pub struct PermitParams {
+    pub owner: String,
+    pub action: PermitAction,
+    /// Must equal the owner's next nonce, returned by the `Nonce` query.
+    pub nonce: u64,
+    pub expires: Expiration,
+    pub chain_id: String,
+    /// Address of this token contract.
+    pub contract: String,
+}
+
+/// An action performed by a permit, as if the owner had sent the matching message.
+#[cw_serde]
+pub enum PermitAction {
+    IncreaseAllowance {
+        spender: String,
+        amount: Uint128,
+        expires: Option<Expiration>,
+    },
+    Transfer { recipient: String, amount: Uint128 },
 }
 
 /// Queries that can read the contract's state.
 #[cw_serde]
 #[derive(QueryResponses)]
 pub enum QueryMsg {
-    /// Returns information about the token (name, symbol, decimals, total supply, owner).
+    /// Returns the balance of a specific address, wrapped in a `BalanceResponse`.
+    #[returns(BalanceResponse)]
+    Balance { address: String },
+    /// Returns the balance of `address` at the start of block `height`, or now when unset.
+    #[returns(BalanceResponse)]
+    BalanceAtHeight { address: String, height: Option<u64> },
+    /// Returns information about the token (name, symbol, decimals, total supply).
     #[returns(TokenInfoResponse)]
+    TokenInfo {},
+    /// Returns the total supply at the start of block `height`, or now when unset.
+    #[returns(TotalSupplyResponse)]
+    TotalSupplyAtHeight { height: Option<u64> },
+    /// Returns who can mint new tokens, if anyone.
+    #[returns(Option<MinterResponse>)]
+    Minter {},
+    /// Returns the marketing information of the token, including its logo URL.
+    #[returns(MarketingInfoResponse)]
+    MarketingInfo {},
+    /// Returns the embedded logo of the token. Fails when the logo is only available as a URL.
+    #[returns(DownloadLogoResponse)]
+    DownloadLogo {},
+    /// Lists all accounts that hold a balance, ordered by address.
+    #[returns(AllAccountsResponse)]
+    AllAccounts {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Returns the current owner and the pending ownership transfer, if any.
+    #[returns(PendingOwnerResponse)]
+    PendingOwner {},
+    /// Returns which operations are paused.
+    #[returns(PauseStatusResponse)]
+    PauseStatus {},
+    /// Lists frozen accounts and the reason they were frozen, ordered by address.
+    #[returns(FrozenAccountsResponse)]
+    FrozenAccounts {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Returns the voting weight delegated to `address` at the start of block `height`, or now when unset.
+    #[returns(VotingPowerResponse)]
+    VotingPower { address: String, height: Option<u64> },
+    /// Returns who `address` has delegated its voting weight to, if anyone.
+    #[returns(DelegatesResponse)]
+    Delegates { address: String },
+    /// Returns how much `address` has staked, and the total staked.
+    #[returns(StakedResponse)]
+    Staked { address: String },
+    /// Returns the staking rewards `address` can claim now.
+    #[returns(PendingRewardsResponse)]
+    PendingRewards { address: String },
+    /// Returns the tokens `address` is unbonding and when each entry can be claimed.
+    #[returns(UnbondingEntriesResponse)]
+    UnbondingEntries { address: String },
+    /// Returns an airdrop stage and how much of it has been claimed.
+    #[returns(MerkleStageResponse)]
+    MerkleStage { stage: u8 },
+    /// Returns whether `address` has claimed airdrop `stage`.
+    #[returns(IsClaimedResponse)]
+    IsClaimed { stage: u8, address: String },
+    /// Returns the nonce the next permit of `address` must use.
+    #[returns(NonceResponse)]
+    Nonce { address: String },
+    /// Returns the native denom wrapping this token, if created, and how many tokens back it.
+    #[returns(NativeDenomResponse)]
+    NativeDenom {},
+    /// Lists connected ICS-20 channels, ordered by channel ID.
+    #[returns(ListChannelsResponse)]
+    ListChannels {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Returns the transfer fee and how it is split.
+    #[returns(FeeConfigResponse)]
+    FeeConfig {},
+    /// Returns whether `address` holds `role`.
+    #[returns(HasRoleResponse)]
+    HasRole { role: Role, address: String },
+    /// Lists the members of `role`, ordered by address.
+    #[returns(RoleMembersResponse)]
+    ListRoleMembers {
+        role: Role,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Returns information about the token (name, symbol, decimals, total supply, owner).
+    /// Deprecated: use `TokenInfo` instead.
+    #[returns(GetTokenInfoResponse)]
     GetTokenInfo {},
-    /// Returns the balance of a specific address.
+    /// Returns the balance of a specific address as a bare amount.
+    /// Deprecated: use `Balance` instead.
     #[returns(Uint128)]
     GetBalance { address: String },
+    /// Returns the metadata URL of the token.
+    /// Deprecated: use `MarketingInfo` instead.
+    #[returns(MetadataResponse)]
+    GetMetadata {},
     /// Returns vesting information for a specific address.
     #[returns(VestingInfoResponse)]
-    GetVestingInfo {
-        address: String,
-        start_after: Option<u64>, // Optional timestamp to start after
-        limit: Option<u32>,      // Optional limit on the number of results
-    },
+    GetVestingInfo { address: String },
     /// Returns pool release information for a specific address.
     #[returns(PoolReleaseInfoResponse)]
-    GetPoolReleaseInfo {
+    GetPoolReleaseInfo { address: String },
+    /// Returns what `address` can release right now from its vesting and pool release records.
+    #[returns(ClaimableResponse)]
+    Claimable { address: String },
+    /// Projects the vesting and pool release records of `address` to `at_time` (unix seconds).
+    /// Height-based schedules are projected to `at_height`, or the current height when unset.
+    #[returns(VestingProjectionResponse)]
+    VestingProjection {
         address: String,
-        start_after: Option<u64>,
+        at_time: u64,
+        at_height: Option<u64>,
+    },
+    /// Returns the keeper tip and how much is left in the reserve it is paid from.
+    #[returns(KeeperInfoResponse)]
+    KeeperInfo {},
+    /// Lists all vesting records, ordered by beneficiary address.
+    #[returns(VestingAccountsResponse)]
+    ListVestingAccounts {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Returns how many tokens `spender` may still move on behalf of `owner`.
+    #[returns(AllowanceResponse)]
+    Allowance { owner: String, spender: String },
+    /// Lists all allowances granted by `owner`, ordered by spender address.
+    #[returns(AllAllowancesResponse)]
+    AllAllowances {
+        owner: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Lists all allowances granted to `spender`, ordered by owner address.
+    #[returns(AllSpenderAllowancesResponse)]
+    AllSpenderAllowances {
+        spender: String,
+        start_after: Option<String>,
         limit: Option<u32>,
     },
 }
 
-/// Response for the `GetTokenInfo` query.
+/// Response for the `Balance` query.
+#[cw_serde]
+pub struct BalanceResponse {
+    pub balance: Uint128,
+}
+
+/// Response for the `TokenInfo` query.
 #[cw_serde]
 pub struct TokenInfoResponse {
     pub name: String,
     pub symbol: String,
     pub decimals: u8,
     pub total_supply: Uint128,
-    pub owner: String,
+}
+
+/// Response for the `TotalSupplyAtHeight` query.
+#[cw_serde]
+pub struct TotalSupplyResponse {
+    pub total_supply: Uint128,
+}
+
+/// Response for the `Minter` query.
+#[cw_serde]
+pub struct MinterResponse {
+    pub minter: String,
+    pub cap: Option<Uint128>,
+    /// How much can be minted right now, or `None` when unlimited.
+    pub mintable: Option<Uint128>,
+}
+
+/// Where the token logo can be found.
+#[cw_serde]
+pub enum LogoInfo {
+    /// The logo is hosted at the given URL.
+    Url(String),
+    /// The logo is stored in the contract and can be fetched with `DownloadLogo`.
+    Embedded,
+}
+
+/// Response for the `MarketingInfo` query.
+#[cw_serde]
+#[derive(Default)]
+pub struct MarketingInfoResponse {
+    pub project: Option<String>,
+    pub description: Option<String>,
+    pub logo: Option<LogoInfo>,
+    pub marketing: Option<Addr>,
+}
+
+/// Response for the `DownloadLogo` query.
+#[cw_serde]
+pub struct DownloadLogoResponse {
+    pub mime_type: String,
+    pub data: Binary,
+}
+
+/// Response for the `AllAccounts` query.
+#[cw_serde]
+pub struct AllAccountsResponse {
+    pub accounts: Vec<String>,
+}
+
+/// Response for the `PauseStatus` query.
+#[cw_serde]
+pub struct PauseStatusResponse {
+    pub transfers: bool,
+    pub burns: bool,
+    pub vesting_releases: bool,
+    pub pool_releases: bool,
+    pub minting: bool,
+}
+
+/// A single frozen account returned by `FrozenAccounts`.
+#[cw_serde]
+pub struct FrozenAccount {
+    pub address: String,
+    pub reason: String,
+}
+
+/// Response for the `FrozenAccounts` query.
+#[cw_serde]
+pub struct FrozenAccountsResponse {
+    pub accounts: Vec<FrozenAccount>,
+}
+
+/// Response for the `VotingPower` query.
+#[cw_serde]
+pub struct VotingPowerResponse {
+    pub power: Uint128,
+}
+
+/// Response for the `Delegates` query.
+#[cw_serde]
+pub struct DelegatesResponse {
+    pub delegate: Option<String>,
+}
+
+/// Response for the `Staked` query.
+#[cw_serde]
+pub struct StakedResponse {
+    pub staked: Uint128,
+    pub total_staked: Uint128,
+}
+
+/// Response for the `PendingRewards` query.
+#[cw_serde]
+pub struct PendingRewardsResponse {
+    pub rewards: Uint128,
+}
+
+/// Response for the `UnbondingEntries` query.
+#[cw_serde]
+pub struct UnbondingEntriesResponse {
+    pub entries: Vec<UnbondingEntry>,
+}
+
+/// Response for the `MerkleStage` query.
+#[cw_serde]
+pub struct MerkleStageResponse {
+    pub stage: u8,
+    pub root: String,
+    pub expiration: Expiration,
+    pub total_amount: Uint128,
+    pub claimed_amount: Uint128,
+    pub reclaimed: bool,
+}
+
+/// Response for the `IsClaimed` query.
+#[cw_serde]
+pub struct IsClaimedResponse {
+    pub is_claimed: bool,
+}
+
+/// Response for the `Nonce` query.
+#[cw_serde]
+pub struct NonceResponse {
+    pub nonce: u64,
+}
+
+/// Response for the `NativeDenom` query.
+#[cw_serde]
+pub struct NativeDenomResponse {
+    pub denom: Option<String>,
+    /// Equal to the native denom's supply.
+    pub escrowed: Uint128,
+}
+
+/// A connected channel, as returned by `ListChannels`.
+#[cw_serde]
+pub struct ChannelResponse {
+    pub channel_id: String,
+    pub counterparty_endpoint: IbcEndpoint,
+    pub connection_id: String,
+    /// Whether the channel is still on the allowlist.
+    pub allowed: bool,
+    /// Tokens sent over the channel and not yet returned.
+    pub escrowed: Uint128,
+}
+
+/// Response for the `ListChannels` query.
+#[cw_serde]
+pub struct ListChannelsResponse {
+    pub channels: Vec<ChannelResponse>,
+}
+
+/// Response for the `FeeConfig` query.
+#[cw_serde]
+pub struct FeeConfigResponse {
+    pub rate_bps: u16,
+    pub treasury_share_bps: u16,
+    pub burn_share_bps: u16,
+    pub pool_share_bps: u16,
+}
+
+/// Response for the `HasRole` query.
+#[cw_serde]
+pub struct HasRoleResponse {
+    pub has_role: bool,
+}
+
+/// Response for the `ListRoleMembers` query.
+#[cw_serde]
+pub struct RoleMembersResponse {
+    pub members: Vec<String>,
+}
+
+/// Response for the deprecated `GetTokenInfo` query.
+#[cw_serde]
+pub struct GetTokenInfoResponse {
+    pub name: String,
+    pub symbol: String,
+    pub decimals: u8,
+    pub total_supply: Uint128,
+    pub owner: Option<String>,
+}
+
+/// Response for the `PendingOwner` query.
+#[cw_serde]
+pub struct PendingOwnerResponse {
+    pub owner: Option<String>,
+    pub pending_owner: Option<String>,
+    pub expiry: Option<Expiration>,
 }
 
 /// Response for the `GetVestingInfo` query.
 #[cw_serde]
 pub struct VestingInfoResponse {
     pub amount: Uint128,
+    pub released: Uint128,
     pub start_time: u64,
-    pub release_schedule: Vec<(u64, Uint128)>,
+    pub schedule: Schedule,
+    pub revoker: Option<String>,
 }
 
 /// Response for the `GetPoolReleaseInfo` query.
 #[cw_serde]
 pub struct PoolReleaseInfoResponse {
     pub amount: Uint128,
-    pub release_schedule: Vec<(u64, Uint128)>,
+    pub released: Uint128,
+    pub schedule: Schedule,
 }
-pub struct MetadataResponse {
-    pub metadata_url: String,
+
+/// The state of a vesting or pool release record at a given block.
+#[cw_serde]
+pub struct ReleaseStatus {
+    /// Vested but not yet released.
+    pub claimable_now: Uint128,
+    /// Released so far.
+    pub total_released: Uint128,
+    /// Not vested yet.
+    pub still_locked: Uint128,
+    /// Next time more tokens unlock, for time-based schedules.
+    pub next_unlock_time: Option<u64>,
+    /// Next height at which more tokens unlock, for height-based schedules.
+    pub next_unlock_height: Option<u64>,
+}
+
+/// Response for the `Claimable` query.
+#[cw_serde]
+pub struct ClaimableResponse {
+    pub vesting: Option<ReleaseStatus>,
+    pub pool: Option<ReleaseStatus>,
+}
+
+/// Response for the `VestingProjection` query.
+#[cw_serde]
+pub struct VestingProjectionResponse {
+    pub at_time: u64,
+    pub at_height: u64,
+    pub vesting: Option<ReleaseStatus>,
+    pub pool: Option<ReleaseStatus>,
+    /// Current balance plus everything that would be claimable at the projected point.
+    pub projected_balance: Uint128,
+}
+
+/// Response for the `KeeperInfo` query.
+#[cw_serde]
+pub struct KeeperInfoResponse {
+    pub tip: Uint128,
+    pub min_release: Uint128,
+    pub reserve: Uint128,
 }
 
-pub struct GetCountResponse {
-    pub count: u64,
+/// A single vesting record returned by `ListVestingAccounts`.
+#[cw_serde]
+pub struct VestingAccount {
+    pub address: String,
+    pub amount: Uint128,
+    pub released: Uint128,
+    pub start_time: u64,
+    pub schedule: Schedule,
+    pub revoker: Option<String>,
+}
+
+/// Response for the `ListVestingAccounts` query.
+#[cw_serde]
+pub struct VestingAccountsResponse {
+    pub accounts: Vec<VestingAccount>,
+}
+
+/// Response for the `Allowance` query.
+#[cw_serde]
+#[derive(Default)]
+pub struct AllowanceResponse {
+    pub allowance: Uint128,
+    pub expires: Expiration,
+}
+
+/// A single allowance granted by the queried owner.
+#[cw_serde]
+pub struct AllowanceInfo {
+    pub spender: String,
+    pub allowance: Uint128,
+    pub expires: Expiration,
+}
+
+/// Response for the `AllAllowances` query.
+#[cw_serde]
+pub struct AllAllowancesResponse {
+    pub allowances: Vec<AllowanceInfo>,
+}
+
+/// A single allowance granted to the queried spender.
+#[cw_serde]
+pub struct SpenderAllowanceInfo {
+    pub owner: String,
+    pub allowance: Uint128,
+    pub expires: Expiration,
+}
+
+/// Response for the `AllSpenderAllowances` query.
+#[cw_serde]
+pub struct AllSpenderAllowancesResponse {
+    pub allowances: Vec<SpenderAllowanceInfo>,
+}
+
+/// Response for the deprecated `GetMetadata` query.
+#[cw_serde]
+pub struct MetadataResponse {
+    pub metadata_url: String,
 }
\ No newline at end of file
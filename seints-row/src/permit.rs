@@ -0,0 +1,17 @@
+use cosmwasm_std::{to_json_vec, CanonicalAddr, StdResult};
+use ripemd::Ripemd160;
+use sha2::{Digest, Sha256};
+
+use crate::msg::PermitParams;
+
+/// Returns the 32 byte hash a holder signs to authorize `params`: the SHA-256 of their JSON serialization.
+pub fn digest(params: &PermitParams) -> StdResult<[u8; 32]> {
+    Ok(Sha256::digest(to_json_vec(params)?).into())
+}
+
+/// Returns the canonical account address of a compressed secp256k1 public key, derived the way Cosmos SDK
+/// chains derive it: RIPEMD-160 of the SHA-256 of the key.
+pub fn pubkey_to_canonical(pubkey: &[u8]) -> CanonicalAddr {
+    let hash = Ripemd160::digest(Sha256::digest(pubkey));
+    CanonicalAddr::from(hash.as_slice())
+}
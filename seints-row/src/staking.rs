@@ -0,0 +1,114 @@
+use cosmwasm_std::{Decimal256, StdResult, Uint128, Uint256};
+
+use crate::state::{Stake, StakingState};
+
+impl StakingState {
+    /// Returns the last second rewards accrue until, which is `now` unless the reward period has ended.
+    fn last_applicable(&self, now: u64) -> u64 {
+        now.min(self.period_finish)
+    }
+
+    /// Returns the rewards accrued per staked token up to `now`.
+    /// Nothing accrues while nothing is staked; `accrue` carries those rewards forward.
+    pub fn reward_per_token_at(&self, now: u64) -> Decimal256 {
+        if self.total_staked.is_zero() {
+            return self.reward_per_token;
+        }
+        let elapsed = self.last_applicable(now).saturating_sub(self.last_update);
+        let accrued = Uint256::from(self.reward_rate) * Uint256::from(elapsed);
+        self.reward_per_token + Decimal256::from_ratio(accrued, self.total_staked)
+    }
+
+    /// Moves the accumulator forward to `now`. Must run before `total_staked` or the reward rate changes.
+    /// While nothing is staked the reward period is pushed back instead, so no rewards are stranded.
+    pub fn accrue(&mut self, now: u64) {
+        if !self.total_staked.is_zero() {
+            self.reward_per_token = self.reward_per_token_at(now);
+        } else if self.last_update < self.period_finish {
+            self.period_finish += now.saturating_sub(self.last_update);
+        }
+        self.last_update = self.last_update.max(self.last_applicable(now));
+    }
+
+    /// Returns the rewards still to be paid out by the running reward period after `now`.
+    pub fn remaining_rewards(&self, now: u64) -> Uint128 {
+        let remaining = self.period_finish.saturating_sub(now);
+        self.reward_rate * Uint128::from(remaining)
+    }
+}
+
+impl Stake {
+    /// Returns the rewards earned so far, given the accumulator value `reward_per_token`.
+    pub fn earned(&self, reward_per_token: Decimal256) -> StdResult<Uint128> {
+        let accrued = Uint256::from(self.amount).mul_floor(reward_per_token - self.reward_per_token_paid);
+        Ok(self.pending_rewards.checked_add(Uint128::try_from(accrued)?)?)
+    }
+
+    /// Moves everything earned up to `reward_per_token` into `pending_rewards`.
+    pub fn settle(&mut self, reward_per_token: Decimal256) -> StdResult<()> {
+        self.pending_rewards = self.earned(reward_per_token)?;
+        self.reward_per_token_paid = reward_per_token;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rewards_are_split_by_stake_and_time() {
+        let mut staking = StakingState {
+            total_staked: Uint128::new(100),
+            reward_rate: Uint128::new(10),
+            period_finish: 100,
+            ..StakingState::default()
+        };
+        let mut alice = Stake {
+            amount: Uint128::new(100),
+            ..Stake::default()
+        };
+
+        // Alice has the pool to herself for 10 seconds
+        staking.accrue(10);
+        alice.settle(staking.reward_per_token).unwrap();
+        assert_eq!(Uint128::new(100), alice.pending_rewards);
+
+        // Bob joins with three times her stake and takes three quarters from then on
+        let mut bob = Stake {
+            amount: Uint128::new(300),
+            reward_per_token_paid: staking.reward_per_token,
+            ..Stake::default()
+        };
+        staking.total_staked += bob.amount;
+        let rpt = staking.reward_per_token_at(30);
+        assert_eq!(Uint128::new(150), alice.earned(rpt).unwrap());
+        assert_eq!(Uint128::new(150), bob.earned(rpt).unwrap());
+
+        // Nothing accrues after the period ends
+        bob.settle(staking.reward_per_token_at(1_000)).unwrap();
+        assert_eq!(Uint128::new(675), bob.pending_rewards);
+        assert_eq!(Uint128::zero(), staking.remaining_rewards(1_000));
+    }
+
+    #[test]
+    fn rewards_wait_for_the_first_stake() {
+        let mut staking = StakingState {
+            reward_rate: Uint128::new(10),
+            period_finish: 100,
+            ..StakingState::default()
+        };
+
+        // Nobody staked for 40 seconds, so the whole period is still ahead
+        staking.accrue(40);
+        assert_eq!(140, staking.period_finish);
+        assert_eq!(Uint128::new(1_000), staking.remaining_rewards(40));
+
+        let alice = Stake {
+            amount: Uint128::new(100),
+            ..Stake::default()
+        };
+        staking.total_staked += alice.amount;
+        assert_eq!(Uint128::new(1_000), alice.earned(staking.reward_per_token_at(1_000)).unwrap());
+    }
+}
@@ -1,8 +1,11 @@
-use cosmwasm_std::{Addr, Timestamp, Uint128};
-use cw_storage_plus::{Item, Map};
+use cosmwasm_std::{Addr, Decimal256, Empty, IbcEndpoint, Timestamp, Uint128};
+use cw_storage_plus::{Item, Map, SnapshotItem, SnapshotMap, Strategy};
+use cw_utils::Expiration;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
+use crate::vesting::Schedule;
+
 
 /// Represents the global information about the token, including its name, symbol, decimals, total supply, and owner.
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -11,46 +14,353 @@ pub struct TokenInfo {
     pub symbol: String,
     pub decimals: u8,
     pub total_supply: Uint128,
-    pub owner: Addr,
+    /// `None` once ownership has been renounced.
+    pub owner: Option<Addr>,
+    /// `None` when minting is disabled.
+    #[serde(default)]
+    pub mint: Option<MinterData>,
 }
 
-/// Represents vesting information for the owner, including the total amount, start time, and release schedule.
+/// Who may mint new tokens, and how many.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MinterData {
+    pub minter: Addr,
+    /// Upper bound on the total supply.
+    pub cap: Option<Uint128>,
+    /// Upper bound on `minted` over time.
+    pub schedule: Option<Schedule>,
+    /// Tokens minted so far, not counting the initial supply.
+    pub minted: Uint128,
+}
+
+/// An ownership transfer proposed by the current owner, waiting for the new owner to accept it.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PendingOwner {
+    pub new_owner: Addr,
+    pub expiry: Option<Expiration>,
+}
+
+/// Represents locked tokens of a beneficiary, including the total amount, how much has been released, and the
+/// schedule it vests on.
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct VestingInfo {
     pub amount: Uint128,
+    pub released: Uint128,
     pub start_time: Timestamp,
-    pub release_schedule: Vec<(Timestamp, Uint128)>,
+    pub schedule: Schedule,
+    /// Address allowed to revoke the unvested remainder, if the vesting is revocable.
+    pub revoker: Option<Addr>,
+}
+
+/// Represents gradual release information for the pool. Pool releases share the vesting record layout.
+pub type PoolReleaseInfo = VestingInfo;
+
+/// Contract-wide configuration, such as which address receives the pool allocation and where clawed back
+/// vesting goes.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Config {
+    pub pool: Option<Addr>,
+    pub treasury: Addr,
+    /// Paid from `KEEPER_RESERVE` to whoever releases tokens on a beneficiary's behalf.
+    #[serde(default)]
+    pub keeper_tip: Uint128,
+    /// Smallest release that earns `keeper_tip`, so dust releases cannot drain the reserve.
+    #[serde(default)]
+    pub keeper_min_release: Uint128,
+    /// Whether still-locked `VESTING` amounts count toward the beneficiary's voting weight.
+    #[serde(default)]
+    pub vesting_votes: bool,
+    /// Seconds unstaked tokens wait before they can be claimed.
+    #[serde(default)]
+    pub unbonding_period: u64,
+    /// Most recipients a single `BatchTransfer` may pay, and most beneficiaries of a single `ReleaseBatch`.
+    #[serde(default = "default_max_batch_size")]
+    pub max_batch_size: u32,
+}
+
+/// Batch size limit of new contracts and of configs stored before the limit existed.
+pub const DEFAULT_MAX_BATCH_SIZE: u32 = 100;
+
+fn default_max_batch_size() -> u32 {
+    DEFAULT_MAX_BATCH_SIZE
+}
+
+/// A privilege that can be granted to an address independently of ownership.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Role {
+    /// May pause and unpause the token.
+    Pauser,
+    /// May update the token metadata.
+    MetadataManager,
+    /// May create vesting records.
+    VestingAdmin,
+    /// May freeze and unfreeze accounts.
+    Compliance,
+    /// May change the transfer fee and who is exempt from it.
+    FeeManager,
+}
+
+impl Role {
+    /// Returns the name the role is stored under.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Role::Pauser => "pauser",
+            Role::MetadataManager => "metadata_manager",
+            Role::VestingAdmin => "vesting_admin",
+            Role::Compliance => "compliance",
+            Role::FeeManager => "fee_manager",
+        }
+    }
+
+    /// Every role, in the order they are granted to a new owner.
+    pub const ALL: [Role; 5] = [
+        Role::Pauser,
+        Role::MetadataManager,
+        Role::VestingAdmin,
+        Role::Compliance,
+        Role::FeeManager,
+    ];
+}
+
+/// A group of operations that can be paused on its own.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Operation {
+    /// Transfers, sends and other moves between balances.
+    Transfers,
+    /// Burns, including burns through an allowance.
+    Burns,
+    /// Releases of vesting records.
+    VestingReleases,
+    /// Releases of pool release records.
+    PoolReleases,
+    /// Minting new tokens.
+    Minting,
+}
+
+impl Operation {
+    /// Every operation, used when pausing or unpausing everything at once.
+    pub const ALL: [Operation; 5] = [
+        Operation::Transfers,
+        Operation::Burns,
+        Operation::VestingReleases,
+        Operation::PoolReleases,
+        Operation::Minting,
+    ];
+
+    /// Returns the name reported in errors and attributes.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Operation::Transfers => "transfers",
+            Operation::Burns => "burns",
+            Operation::VestingReleases => "vesting_releases",
+            Operation::PoolReleases => "pool_releases",
+            Operation::Minting => "minting",
+        }
+    }
+}
+
+/// Which operations are currently paused. Everything runs until a pauser flips a switch.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, JsonSchema)]
+pub struct PauseState {
+    pub transfers: bool,
+    pub burns: bool,
+    pub vesting_releases: bool,
+    pub pool_releases: bool,
+    pub minting: bool,
+}
+
+impl PauseState {
+    /// Returns the switch for `operation`.
+    pub fn switch(&mut self, operation: Operation) -> &mut bool {
+        match operation {
+            Operation::Transfers => &mut self.transfers,
+            Operation::Burns => &mut self.burns,
+            Operation::VestingReleases => &mut self.vesting_releases,
+            Operation::PoolReleases => &mut self.pool_releases,
+            Operation::Minting => &mut self.minting,
+        }
+    }
+
+    /// Returns whether `operation` is paused.
+    pub fn is_paused(&self, operation: Operation) -> bool {
+        match operation {
+            Operation::Transfers => self.transfers,
+            Operation::Burns => self.burns,
+            Operation::VestingReleases => self.vesting_releases,
+            Operation::PoolReleases => self.pool_releases,
+            Operation::Minting => self.minting,
+        }
+    }
+}
+
+/// The fee charged on transfers, in basis points of the amount, and how it is split.
+/// The shares are basis points of the fee and add up to 10,000.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, JsonSchema)]
+pub struct FeeConfig {
+    pub rate_bps: u16,
+    pub treasury_share_bps: u16,
+    pub burn_share_bps: u16,
+    pub pool_share_bps: u16,
+}
+
+/// Staking totals and the reward-per-token accumulator.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, JsonSchema)]
+pub struct StakingState {
+    pub total_staked: Uint128,
+    /// Rewards accrued per staked token since staking began.
+    pub reward_per_token: Decimal256,
+    /// Rewards distributed per second until `period_finish`.
+    pub reward_rate: Uint128,
+    /// Unix time in seconds at which the current reward period ends.
+    pub period_finish: u64,
+    /// Unix time in seconds up to which `reward_per_token` is accrued.
+    pub last_update: u64,
+}
+
+/// A staker's position and the rewards it has earned.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, JsonSchema)]
+pub struct Stake {
+    pub amount: Uint128,
+    /// Value of the accumulator when `pending_rewards` was last brought up to date.
+    pub reward_per_token_paid: Decimal256,
+    pub pending_rewards: Uint128,
 }
 
-/// Represents gradual release information for the pool, including the total amount and release schedule.
+/// Unstaked tokens waiting for the unbonding period to pass.
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
-pub struct PoolReleaseInfo {
+pub struct UnbondingEntry {
     pub amount: Uint128,
-    pub release_schedule: Vec<(Timestamp, Uint128)>,
+    pub release_at: Timestamp,
+}
+
+/// An airdrop stage: the root of a tree of (address, amount) claims and the tokens escrowed to pay them.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MerkleStage {
+    /// Hex encoded SHA-256 root.
+    pub root: String,
+    /// Claims are rejected once this passes, and the owner may reclaim what is left.
+    pub expiration: Expiration,
+    pub total_amount: Uint128,
+    pub claimed_amount: Uint128,
+    /// The address that funded the stage, which receives reclaimed tokens.
+    pub funder: Addr,
+    pub reclaimed: bool,
+}
+
+/// An ICS-20 channel that has completed its handshake.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ChannelInfo {
+    pub counterparty_endpoint: IbcEndpoint,
+    pub connection_id: String,
+}
+
+/// Represents the amount a spender may move on behalf of an owner, and when that permission expires.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, JsonSchema)]
+pub struct Allowance {
+    pub allowance: Uint128,
+    pub expires: Expiration,
 }
 
 // Token information
 pub const TOKEN_INFO: Item<TokenInfo> = Item::new("token_info");
 
-// Balances of token holders
-pub const BALANCES: Map<&Addr, Uint128> = Map::new("balances");
+// Ownership transfer waiting to be accepted
+pub const PENDING_OWNER: Item<PendingOwner> = Item::new("pending_owner");
+
+// Role members, keyed by (role name, member)
+pub const ROLES: Map<(&str, &Addr), Empty> = Map::new("roles");
+
+// Emergency switches, absent until something is paused for the first time
+pub const PAUSE_STATE: Item<PauseState> = Item::new("pause_state");
+
+// Frozen accounts and why they were frozen
+pub const FROZEN: Map<&Addr, String> = Map::new("frozen");
+
+// Transfer fee, absent until a fee manager sets one
+pub const FEE_CONFIG: Item<FeeConfig> = Item::new("fee_config");
+
+// Addresses that neither pay nor trigger the transfer fee, such as DEX pairs
+pub const FEE_EXEMPT: Map<&Addr, Empty> = Map::new("fee_exempt");
+
+// Balances of token holders, with a checkpoint per block so past balances can be queried
+pub const BALANCES: SnapshotMap<&Addr, Uint128> = SnapshotMap::new(
+    "balances",
+    "balances__checkpoints",
+    "balances__changelog",
+    Strategy::EveryBlock,
+);
+
+// History of `TokenInfo::total_supply`, kept in step with it for queries at past heights
+pub const TOTAL_SUPPLY: SnapshotItem<Uint128> = SnapshotItem::new(
+    "total_supply",
+    "total_supply__checkpoints",
+    "total_supply__changelog",
+    Strategy::EveryBlock,
+);
+
+// Vesting information per beneficiary
+pub const VESTING: Map<&Addr, VestingInfo> = Map::new("vesting");
+
+// Gradual release schedule for the pool
+pub const POOL_RELEASE_SCHEDULE: Map<&Addr, PoolReleaseInfo> = Map::new("pool_release_schedule");
+
+// Contract-wide configuration set at instantiation
+pub const CONFIG: Item<Config> = Item::new("config");
 
-// Vesting information for the owner, with optional pagination support
-pub const VESTING: Map<(&Addr, Option<u64>, Option<u32>), VestingInfo> = Map::new("vesting");
+// Who each holder has delegated its voting weight to
+pub const DELEGATES: Map<&Addr, Addr> = Map::new("delegates");
 
-// Gradual release schedule for the pool, with optional pagination support
-pub const POOL_RELEASE_SCHEDULE: Map<(&Addr, Option<u64>, Option<u32>), PoolReleaseInfo> =
-    Map::new("pool_release_schedule");
+// Voting weight delegated to each delegatee, with a checkpoint per block
+pub const VOTES: SnapshotMap<&Addr, Uint128> = SnapshotMap::new(
+    "votes",
+    "votes__checkpoints",
+    "votes__changelog",
+    Strategy::EveryBlock,
+);
+
+// Staking totals and reward accumulator, absent until the first stake or reward funding
+pub const STAKING: Item<StakingState> = Item::new("staking");
+
+// Staked tokens and earned rewards per staker
+pub const STAKES: Map<&Addr, Stake> = Map::new("stakes");
+
+// Unbonding tokens per staker, oldest first
+pub const UNBONDING: Map<&Addr, Vec<UnbondingEntry>> = Map::new("unbonding");
+
+// Airdrop stages by stage number
+pub const MERKLE_STAGES: Map<u8, MerkleStage> = Map::new("merkle_stages");
+
+// Addresses that have claimed, keyed by (stage, address)
+pub const AIRDROP_CLAIMS: Map<(u8, &Addr), Empty> = Map::new("airdrop_claims");
+
+// Next permit nonce per holder; a permit must use it exactly, so each can be submitted once
+pub const NONCES: Map<&Addr, u64> = Map::new("nonces");
+
+// Token factory denom that wraps this token, absent until the owner creates it
+pub const NATIVE_DENOM: Item<String> = Item::new("native_denom");
+
+// Tokens escrowed 1:1 against the native denom in circulation
+pub const NATIVE_ESCROW: Item<Uint128> = Item::new("native_escrow");
+
+// Channels the owner allows to open and carry transfers
+pub const CHANNEL_ALLOWLIST: Map<&str, Empty> = Map::new("channel_allowlist");
+
+// Connected ICS-20 channels
+pub const CHANNELS: Map<&str, ChannelInfo> = Map::new("channels");
+
+// Tokens sent over each channel and not yet returned, which is all that may come back through it
+pub const CHANNEL_ESCROW: Map<&str, Uint128> = Map::new("channel_escrow");
+
+// Tokens set aside to pay keeper tips
+pub const KEEPER_RESERVE: Item<Uint128> = Item::new("keeper_reserve");
 
 // Metadata URL for the token
 pub const METADATA_URL: Item<String> = Item::new("metadata_url");
-pub struct VestingInfo {
-    pub amount: Uint128,
-    pub start_time: Timestamp,
-    pub release_schedule: Vec<(Timestamp, Uint128)>,
-}
 
-pub struct PoolReleaseInfo {
-    pub amount: Uint128,
-    pub release_schedule: Vec<(Timestamp, Uint128)>,
-}
\ No newline at end of file
+// Allowances granted by an owner to a spender, keyed by (owner, spender)
+pub const ALLOWANCES: Map<(&Addr, &Addr), Allowance> = Map::new("allowance");
+
+// Mirror of `ALLOWANCES` keyed by (spender, owner), used to list allowances per spender
+pub const ALLOWANCES_SPENDER: Map<(&Addr, &Addr), Allowance> = Map::new("allowance_spender");
@@ -0,0 +1,25 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, Coin, CustomMsg};
+
+/// Custom messages understood by Sei's token factory module, used to mint and burn the native denom that wraps
+/// this token. The variants mirror `SeiMsg` from `sei-cosmwasm`, so they serialize the way Sei's bindings expect.
+///
+/// Every response of the contract is a `Response<SeiMsg>`, so it can only run on a chain that accepts these
+/// custom messages, and cw-multi-test apps need a custom module that handles them. The lock-in to Sei is
+/// deliberate; running elsewhere means removing the native denom.
+#[cw_serde]
+pub enum SeiMsg {
+    /// Creates `factory/{contract}/{subdenom}`, administered by the contract.
+    CreateDenom { subdenom: String },
+    /// Mints `amount` to the contract, which must administer its denom.
+    MintTokens { amount: Coin },
+    /// Burns `amount` from the contract's own balance.
+    BurnTokens { amount: Coin },
+}
+
+impl CustomMsg for SeiMsg {}
+
+/// Returns the full name of the denom the token factory creates for `contract` under `subdenom`.
+pub fn full_denom(contract: &Addr, subdenom: &str) -> String {
+    format!("factory/{contract}/{subdenom}")
+}
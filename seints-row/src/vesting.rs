@@ -0,0 +1,314 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, BlockInfo, Storage, Uint128};
+use cw_storage_plus::Map;
+
+use crate::error::ContractError;
+use crate::msg::ReleaseStatus;
+use crate::state::VestingInfo;
+
+/// What the points of a vesting curve are measured in.
+#[cw_serde]
+#[derive(Copy, Eq)]
+pub enum Clock {
+    /// Unix time in seconds.
+    Time,
+    /// Block height.
+    Height,
+}
+
+/// The cumulative amount vested as a function of a point in time (or height).
+#[cw_serde]
+pub enum Curve {
+    /// The same amount is vested at every point.
+    Constant { y: Uint128 },
+    /// Vests `amount` linearly from `start` to `end`, but nothing unlocks before `cliff`.
+    /// At the cliff, everything accrued since `start` unlocks at once.
+    CliffLinear {
+        start: u64,
+        cliff: u64,
+        end: u64,
+        amount: Uint128,
+    },
+    /// Interpolates linearly between (point, amount) pairs.
+    /// The curve is flat before the first point and after the last one.
+    PiecewiseLinear { points: Vec<(u64, Uint128)> },
+    /// Jumps to the amount of each (point, amount) step once the point is reached.
+    /// Nothing is vested before the first step.
+    Stepped { steps: Vec<(u64, Uint128)> },
+}
+
+impl Curve {
+    /// Returns the cumulative amount vested at point `x`.
+    pub fn value(&self, x: u64) -> Uint128 {
+        match self {
+            Curve::Constant { y } => *y,
+            Curve::CliffLinear { start, cliff, end, amount } => {
+                if x < *cliff {
+                    Uint128::zero()
+                } else if x >= *end {
+                    *amount
+                } else {
+                    amount.multiply_ratio(x - start, end - start)
+                }
+            }
+            Curve::PiecewiseLinear { points } => {
+                // Find the first point after `x`, interpolating from the one before it
+                match points.iter().position(|(px, _)| *px > x) {
+                    Some(0) => points[0].1,
+                    Some(i) => {
+                        let (x0, y0) = points[i - 1];
+                        let (x1, y1) = points[i];
+                        y0 + (y1 - y0).multiply_ratio(x - x0, x1 - x0)
+                    }
+                    None => points.last().map(|(_, y)| *y).unwrap_or_default(),
+                }
+            }
+            Curve::Stepped { steps } => steps
+                .iter()
+                .take_while(|(px, _)| *px <= x)
+                .last()
+                .map(|(_, y)| *y)
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Returns the first point after `x` at which the vested amount starts increasing, if any.
+    /// Points are whole seconds or blocks, so while a linear segment is running this is `x + 1`.
+    pub fn next_unlock(&self, x: u64) -> Option<u64> {
+        match self {
+            Curve::Constant { .. } => None,
+            Curve::CliffLinear { cliff, end, .. } => {
+                if x < *cliff {
+                    Some(*cliff)
+                } else if x + 1 < *end {
+                    Some(x + 1)
+                } else if x < *end {
+                    Some(*end)
+                } else {
+                    None
+                }
+            }
+            Curve::PiecewiseLinear { points } => points
+                .windows(2)
+                .filter(|w| w[1].1 > w[0].1 && w[1].0 > x)
+                .map(|w| w[0].0.max(x) + 1)
+                .next(),
+            Curve::Stepped { steps } => steps
+                .iter()
+                .find(|(px, y)| *px > x && *y > self.value(x))
+                .map(|(px, _)| *px),
+        }
+    }
+
+    /// Returns the amount vested once the curve is complete.
+    pub fn total(&self) -> Uint128 {
+        match self {
+            Curve::Constant { y } => *y,
+            Curve::CliffLinear { amount, .. } => *amount,
+            Curve::PiecewiseLinear { points } => points.last().map(|(_, y)| *y).unwrap_or_default(),
+            Curve::Stepped { steps } => steps.last().map(|(_, y)| *y).unwrap_or_default(),
+        }
+    }
+
+    /// Ensures the curve never decreases and ends with a non-zero amount.
+    pub fn validate(&self) -> Result<(), ContractError> {
+        let valid = match self {
+            Curve::Constant { y } => !y.is_zero(),
+            Curve::CliffLinear { start, cliff, end, amount } => {
+                start <= cliff && cliff <= end && start < end && !amount.is_zero()
+            }
+            Curve::PiecewiseLinear { points: pairs } | Curve::Stepped { steps: pairs } => {
+                !pairs.is_empty()
+                    && pairs.windows(2).all(|w| w[0].0 < w[1].0 && w[0].1 <= w[1].1)
+                    && !self.total().is_zero()
+            }
+        };
+        if valid {
+            Ok(())
+        } else {
+            Err(ContractError::InvalidVestingSchedule {})
+        }
+    }
+}
+
+/// A vesting curve together with the clock its points are measured in.
+#[cw_serde]
+pub struct Schedule {
+    pub clock: Clock,
+    pub curve: Curve,
+}
+
+impl Schedule {
+    /// Returns the point on this schedule's clock that `block` corresponds to.
+    pub fn point(&self, block: &BlockInfo) -> u64 {
+        match self.clock {
+            Clock::Time => block.time.seconds(),
+            Clock::Height => block.height,
+        }
+    }
+
+    /// Returns the cumulative amount vested at `block`.
+    pub fn vested_at(&self, block: &BlockInfo) -> Uint128 {
+        self.curve.value(self.point(block))
+    }
+
+    /// Returns the first point after `block` at which more tokens start vesting, if any.
+    pub fn next_unlock(&self, block: &BlockInfo) -> Option<u64> {
+        self.curve.next_unlock(self.point(block))
+    }
+
+    /// Returns the amount vested once the schedule is complete.
+    pub fn total(&self) -> Uint128 {
+        self.curve.total()
+    }
+
+    /// Ensures the underlying curve is well formed.
+    pub fn validate(&self) -> Result<(), ContractError> {
+        self.curve.validate()
+    }
+}
+
+impl VestingInfo {
+    /// Returns how much of the locked amount has vested at `block`.
+    pub fn vested(&self, block: &BlockInfo) -> Uint128 {
+        self.schedule.vested_at(block).min(self.amount)
+    }
+
+    /// Returns how much has vested at `block` but has not been released yet.
+    pub fn releasable(&self, block: &BlockInfo) -> Uint128 {
+        self.vested(block).saturating_sub(self.released)
+    }
+
+    /// Summarizes the record as seen at `block`, using the same computation as `release`.
+    pub fn status(&self, block: &BlockInfo) -> ReleaseStatus {
+        let next_unlock = self.schedule.next_unlock(block);
+        let (next_unlock_time, next_unlock_height) = match self.schedule.clock {
+            Clock::Time => (next_unlock, None),
+            Clock::Height => (None, next_unlock),
+        };
+        ReleaseStatus {
+            claimable_now: self.releasable(block),
+            total_released: self.released,
+            still_locked: self.amount.saturating_sub(self.vested(block)),
+            next_unlock_time,
+            next_unlock_height,
+        }
+    }
+}
+
+/// Marks everything vested for `beneficiary` up to `block` as released and returns that amount.
+/// Shared by owner vesting and pool releases; the caller credits the returned amount.
+pub fn release(
+    storage: &mut dyn Storage,
+    records: &Map<&Addr, VestingInfo>,
+    beneficiary: &Addr,
+    block: &BlockInfo,
+) -> Result<Uint128, ContractError> {
+    let mut vesting_info = records.load(storage, beneficiary)?;
+    let releasable = vesting_info.releasable(block);
+    vesting_info.released += releasable;
+    records.save(storage, beneficiary, &vesting_info)?;
+    Ok(releasable)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn constant_curve_is_always_vested() {
+        let curve = Curve::Constant { y: Uint128::new(100) };
+        assert_eq!(Uint128::new(100), curve.value(0));
+        assert_eq!(Uint128::new(100), curve.value(u64::MAX));
+    }
+
+    #[test]
+    fn cliff_linear_curve() {
+        let curve = Curve::CliffLinear {
+            start: 100,
+            cliff: 200,
+            end: 500,
+            amount: Uint128::new(4_000),
+        };
+        assert_eq!(Uint128::zero(), curve.value(199));
+        // Everything accrued since `start` unlocks at the cliff
+        assert_eq!(Uint128::new(1_000), curve.value(200));
+        assert_eq!(Uint128::new(3_000), curve.value(400));
+        assert_eq!(Uint128::new(4_000), curve.value(500));
+        assert_eq!(Uint128::new(4_000), curve.value(10_000));
+    }
+
+    #[test]
+    fn piecewise_linear_curve() {
+        let curve = Curve::PiecewiseLinear {
+            points: vec![(10, Uint128::zero()), (20, Uint128::new(100)), (40, Uint128::new(150))],
+        };
+        assert_eq!(Uint128::zero(), curve.value(0));
+        assert_eq!(Uint128::new(50), curve.value(15));
+        assert_eq!(Uint128::new(100), curve.value(20));
+        assert_eq!(Uint128::new(125), curve.value(30));
+        assert_eq!(Uint128::new(150), curve.value(100));
+    }
+
+    #[test]
+    fn stepped_curve() {
+        let curve = Curve::Stepped {
+            steps: vec![(10, Uint128::new(30)), (20, Uint128::new(60)), (30, Uint128::new(90))],
+        };
+        assert_eq!(Uint128::zero(), curve.value(9));
+        assert_eq!(Uint128::new(30), curve.value(10));
+        assert_eq!(Uint128::new(30), curve.value(19));
+        assert_eq!(Uint128::new(90), curve.value(30));
+        assert_eq!(Uint128::new(90), curve.total());
+    }
+
+    #[test]
+    fn next_unlock_points() {
+        let stepped = Curve::Stepped {
+            steps: vec![(10, Uint128::new(30)), (20, Uint128::new(60))],
+        };
+        assert_eq!(Some(10), stepped.next_unlock(0));
+        assert_eq!(Some(20), stepped.next_unlock(10));
+        assert_eq!(None, stepped.next_unlock(20));
+
+        let linear = Curve::CliffLinear {
+            start: 0,
+            cliff: 50,
+            end: 100,
+            amount: Uint128::new(100),
+        };
+        assert_eq!(Some(50), linear.next_unlock(0));
+        assert_eq!(Some(61), linear.next_unlock(60));
+        assert_eq!(None, linear.next_unlock(100));
+
+        let piecewise = Curve::PiecewiseLinear {
+            points: vec![(10, Uint128::zero()), (20, Uint128::zero()), (30, Uint128::new(10))],
+        };
+        assert_eq!(Some(21), piecewise.next_unlock(5));
+        assert_eq!(Some(26), piecewise.next_unlock(25));
+        assert_eq!(None, piecewise.next_unlock(30));
+    }
+
+    #[test]
+    fn validate_rejects_malformed_curves() {
+        let unordered = Curve::Stepped {
+            steps: vec![(20, Uint128::new(30)), (10, Uint128::new(60))],
+        };
+        assert!(unordered.validate().is_err());
+
+        let decreasing = Curve::PiecewiseLinear {
+            points: vec![(10, Uint128::new(60)), (20, Uint128::new(30))],
+        };
+        assert!(decreasing.validate().is_err());
+
+        let cliff_after_end = Curve::CliffLinear {
+            start: 0,
+            cliff: 200,
+            end: 100,
+            amount: Uint128::new(1),
+        };
+        assert!(cliff_after_end.validate().is_err());
+
+        assert!(Curve::Constant { y: Uint128::zero() }.validate().is_err());
+    }
+}